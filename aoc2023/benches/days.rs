@@ -0,0 +1,65 @@
+use aoc2023::{Answer, Timed};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+fn elapsed(part: Option<Timed<Option<Answer>>>) -> Duration {
+    part.map(|p| p.elapsed).unwrap_or(Duration::ZERO)
+}
+
+fn solved(part: &Option<Timed<Option<Answer>>>) -> bool {
+    part.as_ref().is_some_and(|p| p.value.is_some())
+}
+
+/// Benches every registered day with an input, timing each stage through
+/// `Puzzle::run` so that the parts don't include parsing. Filter on the group
+/// name to bench a single day, e.g. `cargo bench -- "2023 day 05"`.
+fn from_registry(c: &mut Criterion) {
+    for puzzle in aoc2023::SOLUTIONS.iter() {
+        let (year, day) = (puzzle.year(), puzzle.day());
+        let path = std::path::PathBuf::from("inputs")
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"));
+        let Ok(input) = std::fs::read_to_string(&path) else {
+            eprintln!("Skipping {year} day {day}: no input at {}", path.display());
+            continue;
+        };
+
+        // unsolved parts have nothing worth timing
+        let run = match puzzle.run(&input, true, true) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("Skipping {year} day {day}: input error at {e}");
+                continue;
+            }
+        };
+        // the input parsed once, so it'll parse every time
+        let run_parts = |part1, part2| puzzle.run(&input, part1, part2).unwrap();
+
+        let mut group = c.benchmark_group(format!("{year} day {day:02}"));
+        group.bench_function("Parse", |b| {
+            b.iter_custom(|iters| (0..iters).map(|_| run_parts(false, false).parse).sum())
+        });
+        if solved(&run.part1) {
+            group.bench_function("Part 1", |b| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| elapsed(run_parts(true, false).part1))
+                        .sum()
+                })
+            });
+        }
+        if solved(&run.part2) {
+            group.bench_function("Part 2", |b| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| elapsed(run_parts(false, true).part2))
+                        .sum()
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, from_registry);
+criterion_main!(benches);
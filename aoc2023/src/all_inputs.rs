@@ -0,0 +1,309 @@
+use crate::runner::{DayResult, PartResult, Status};
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Other inputs for a day (from other accounts, or hand-made edge cases) are
+/// kept in `inputs/YYYY/dayNN/`, one `.txt` file each.
+pub fn inputs_dir(year: u32, day: u32) -> PathBuf {
+    Path::new("inputs")
+        .join(year.to_string())
+        .join(format!("day{day:02}"))
+}
+
+/// The answers each input should give, if we know them, kept next to them in
+/// `answers.toml` with a table per input:
+///
+/// ```toml
+/// [alice]
+/// part1 = "6812"
+/// part2 = "527"
+/// ```
+pub type Expected = BTreeMap<String, [Option<String>; 2]>;
+
+pub fn parse_expected(contents: &str) -> Result<Expected> {
+    let table = contents.parse::<toml::Table>()?;
+    let mut expected = Expected::new();
+    for (name, parts) in table.iter() {
+        let Some(parts) = parts.as_table() else {
+            bail!("Expected '{name}' to be a table");
+        };
+        let mut answers: [Option<String>; 2] = Default::default();
+        for (key, answer) in parts.iter() {
+            let i = match key.as_str() {
+                "part1" => 0,
+                "part2" => 1,
+                _ => bail!("Invalid part '{key}' for '{name}', expected 'part1' or 'part2'"),
+            };
+            answers[i] = Some(match answer {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Integer(n) => n.to_string(),
+                _ => bail!("Answer for '{name}' {key} must be a string or integer"),
+            });
+        }
+        expected.insert(name.clone(), answers);
+    }
+    Ok(expected)
+}
+
+/// A named input for a day, and the answers it should give.
+pub struct NamedInput {
+    pub name: String,
+    pub input: String,
+    pub expected: [Option<String>; 2],
+}
+
+/// Loads every input in the day's directory, ordered by name. A day without
+/// a directory has no extra inputs.
+pub fn load(year: u32, day: u32) -> Result<Vec<NamedInput>> {
+    load_dir(&inputs_dir(year, day))
+}
+
+fn load_dir(dir: &Path) -> Result<Vec<NamedInput>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let answers_path = dir.join("answers.toml");
+    let mut expected = match std::fs::read_to_string(&answers_path) {
+        Ok(contents) => parse_expected(&contents)
+            .with_context(|| format!("Failed to parse {}", answers_path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Expected::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", answers_path.display()))
+        }
+    };
+
+    let mut paths = entries
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to read {}", dir.display()))?;
+    paths.retain(|p| p.extension().is_some_and(|e| e == "txt"));
+    paths.sort();
+
+    let mut inputs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let input = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read input from {}", path.display()))?;
+        inputs.push(NamedInput {
+            expected: expected.remove(&name).unwrap_or_default(),
+            name,
+            input,
+        });
+    }
+    for name in expected.keys() {
+        eprintln!(
+            "Warning: {} has answers for '{name}', but there's no {name}.txt",
+            answers_path.display()
+        );
+    }
+    Ok(inputs)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// Ran fine, but there weren't any answers to check it against.
+    Unchecked,
+    Failed,
+    Panicked,
+}
+
+/// Checks a result against the answers its input should give, marking any
+/// wrong parts as regressed.
+pub fn check(result: &mut DayResult, expected: &[Option<String>; 2]) -> Outcome {
+    let parts = [&mut result.part1, &mut result.part2];
+    if parts.iter().any(|p| p.status == Status::Panicked) {
+        return Outcome::Panicked;
+    }
+    if parts
+        .iter()
+        .any(|p| matches!(p.status, Status::InputError | Status::TimedOut))
+    {
+        return Outcome::Failed;
+    }
+    let mut outcome = Outcome::Unchecked;
+    for (part, expected) in parts.into_iter().zip(expected.iter()) {
+        let Some(expected) = expected else {
+            continue;
+        };
+        if part.status == Status::Skipped {
+            continue;
+        }
+        if answer(part).as_deref() == Some(expected.as_str()) {
+            if outcome == Outcome::Unchecked {
+                outcome = Outcome::Passed;
+            }
+        } else {
+            part.status = Status::Regressed;
+            outcome = Outcome::Failed;
+        }
+    }
+    outcome
+}
+
+fn answer(part: &PartResult) -> Option<String> {
+    part.answer.as_ref().map(|a| a.to_string())
+}
+
+/// A line for each way an input didn't pass, and a count of each outcome.
+pub fn summarise(
+    results: &[DayResult],
+    outcomes: &[Outcome],
+    expected: &[[Option<String>; 2]],
+) -> (Vec<String>, String) {
+    let mut lines = Vec::new();
+    for ((result, outcome), expected) in results.iter().zip(outcomes).zip(expected) {
+        if matches!(outcome, Outcome::Passed | Outcome::Unchecked) {
+            continue;
+        }
+        if let Some(failure) = result.failure() {
+            lines.push(failure);
+            continue;
+        }
+        let parts = [&result.part1, &result.part2];
+        for (i, (part, expected)) in parts.iter().zip(expected).enumerate() {
+            if part.status == Status::Regressed {
+                lines.push(format!(
+                    "{} day {} ({}) part {}: expected {}, got {}",
+                    result.year,
+                    result.day,
+                    result.input.as_deref().unwrap_or_default(),
+                    i + 1,
+                    expected.as_deref().unwrap_or_default(),
+                    answer(part).unwrap_or_else(|| "nothing".to_string())
+                ));
+            }
+        }
+    }
+    let count = |o| outcomes.iter().filter(|&&x| x == o).count();
+    let mut summary = format!(
+        "{} passed, {} failed, {} panicked",
+        count(Outcome::Passed),
+        count(Outcome::Failed),
+        count(Outcome::Panicked)
+    );
+    if count(Outcome::Unchecked) > 0 {
+        summary.push_str(&format!(
+            ", {} without answers to check",
+            count(Outcome::Unchecked)
+        ));
+    }
+    (lines, summary)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc2023::Answer;
+
+    fn result(part1: Option<usize>, status: Status) -> DayResult {
+        DayResult {
+            year: 2023,
+            day: 10,
+            input: Some("alice".to_string()),
+            part1: PartResult {
+                answer: part1.map(Answer::from),
+                status,
+                elapsed: 0.0,
+                memory: None,
+            },
+            part2: PartResult {
+                answer: None,
+                status: Status::Skipped,
+                elapsed: 0.0,
+                memory: None,
+            },
+            parse: 0.0,
+            parse_memory: None,
+            error: (status == Status::Panicked).then(|| "no start found".to_string()),
+        }
+    }
+
+    #[test]
+    fn can_parse_expected_answers() {
+        let expected =
+            parse_expected("[alice]\npart1 = \"6812\"\npart2 = 527\n\n[bob]\npart1 = 1\n").unwrap();
+        assert_eq!(
+            expected["alice"],
+            [Some("6812".to_string()), Some("527".to_string())]
+        );
+        assert_eq!(expected["bob"], [Some("1".to_string()), None]);
+        assert!(parse_expected("alice = 1\n").is_err());
+        assert!(parse_expected("[alice]\npart3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn checks_results() {
+        let expected = [Some("8".to_string()), Some("4".to_string())];
+        let mut passed = result(Some(8), Status::Done);
+        assert_eq!(check(&mut passed, &expected), Outcome::Passed);
+
+        let mut failed = result(Some(7), Status::Done);
+        assert_eq!(check(&mut failed, &expected), Outcome::Failed);
+        assert_eq!(failed.part1.status, Status::Regressed);
+
+        let mut both = result(Some(7), Status::Done);
+        both.part2 = PartResult {
+            answer: Some(Answer::from(4usize)),
+            status: Status::Done,
+            elapsed: 0.0,
+            memory: None,
+        };
+        assert_eq!(check(&mut both, &expected), Outcome::Failed);
+
+        let mut unchecked = result(Some(7), Status::Done);
+        assert_eq!(check(&mut unchecked, &[None, None]), Outcome::Unchecked);
+
+        let mut panicked = result(None, Status::Panicked);
+        assert_eq!(check(&mut panicked, &expected), Outcome::Panicked);
+
+        let results = [passed, failed, unchecked, panicked];
+        let outcomes = [
+            Outcome::Passed,
+            Outcome::Failed,
+            Outcome::Unchecked,
+            Outcome::Panicked,
+        ];
+        let (lines, summary) = summarise(
+            &results,
+            &outcomes,
+            &[expected.clone(), expected.clone(), [None, None], expected],
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "2023 day 10 (alice) part 1: expected 8, got 7",
+                "2023 day 10 (alice) panicked: no start found"
+            ]
+        );
+        assert_eq!(
+            summary,
+            "1 passed, 1 failed, 1 panicked, 1 without answers to check"
+        );
+    }
+
+    #[test]
+    fn loads_inputs_from_the_day_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let day = dir.path().join("day10");
+        std::fs::create_dir(&day).unwrap();
+        std::fs::write(day.join("bob.txt"), "S-7\n").unwrap();
+        std::fs::write(day.join("alice.txt"), "F-S\n").unwrap();
+        std::fs::write(day.join("notes.md"), "not an input").unwrap();
+        std::fs::write(day.join("answers.toml"), "[alice]\npart1 = 8\n").unwrap();
+
+        let inputs = load_dir(&day).unwrap();
+        let names: Vec<_> = inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "bob"]);
+        assert_eq!(inputs[0].input, "F-S\n");
+        assert_eq!(inputs[0].expected, [Some("8".to_string()), None]);
+        assert_eq!(inputs[1].expected, [None, None]);
+        assert!(load_dir(&dir.path().join("day11")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::cli::Part;
+use crate::runner::{DayResult, PartResult, Status};
+use anyhow::{bail, Context, Result};
+use aoc2023::Answer;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Answers we know are correct, so that refactors can be checked against them.
+///
+/// These are stored in a TOML file with one table per day, grouped by year:
+///
+/// ```toml
+/// [2023.day01]
+/// part1 = "142"
+/// part2 = "281"
+/// ```
+pub struct KnownAnswers {
+    path: PathBuf,
+    answers: BTreeMap<Key, String>,
+}
+
+type Key = (u32, u32, Part);
+
+fn parse_day_key(key: &str) -> Option<u32> {
+    key.strip_prefix("day")?.parse().ok()
+}
+
+fn parse_part_key(key: &str) -> Option<Part> {
+    match key {
+        "part1" => Some(Part::One),
+        "part2" => Some(Part::Two),
+        _ => None,
+    }
+}
+
+impl KnownAnswers {
+    /// Loads the answers file, treating a missing file as having no answers.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read answers from {}", path.display()))
+            }
+        };
+        let answers = Self::parse(&contents)
+            .with_context(|| format!("Failed to parse answers from {}", path.display()))?;
+        Ok(KnownAnswers { path, answers })
+    }
+
+    fn parse(contents: &str) -> Result<BTreeMap<Key, String>> {
+        let table = contents.parse::<toml::Table>()?;
+        let mut answers = BTreeMap::new();
+        for (key, days) in table.iter() {
+            let Ok(year) = key.parse::<u32>() else {
+                bail!("Invalid year '{key}', expected something like '2023'");
+            };
+            let Some(days) = days.as_table() else {
+                bail!("Expected '{key}' to be a table");
+            };
+            for (key, parts) in days.iter() {
+                let Some(day) = parse_day_key(key) else {
+                    bail!("Invalid day '{key}', expected something like 'day01'");
+                };
+                let Some(parts) = parts.as_table() else {
+                    bail!("Expected '{year}.{key}' to be a table");
+                };
+                for (key, answer) in parts.iter() {
+                    let Some(part) = parse_part_key(key) else {
+                        bail!("Invalid part '{key}' for {year} day {day}, expected 'part1' or 'part2'");
+                    };
+                    let answer = match answer {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(n) => n.to_string(),
+                        _ => bail!("Answer for {year} day {day} {key} must be a string or integer"),
+                    };
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|a| a.as_str())
+    }
+
+    /// Records an answer if there isn't one already, returning whether it was
+    /// added. Known answers are never overwritten.
+    pub fn record(&mut self, year: u32, day: u32, part: Part, answer: &Answer) -> bool {
+        if self.answers.contains_key(&(year, day, part)) {
+            return false;
+        }
+        self.answers.insert((year, day, part), answer.to_string());
+        true
+    }
+
+    fn to_toml(&self) -> String {
+        let mut out = String::new();
+        let mut current_day = None;
+        for ((year, day, part), answer) in self.answers.iter() {
+            if current_day != Some((*year, *day)) {
+                if current_day.is_some() {
+                    out.push('\n');
+                }
+                out.push_str(&format!("[{year}.day{day:02}]\n"));
+                current_day = Some((*year, *day));
+            }
+            let key = match part {
+                Part::One => "part1",
+                Part::Two => "part2",
+            };
+            out.push_str(&format!(
+                "{key} = {}\n",
+                toml::Value::String(answer.clone())
+            ));
+        }
+        out
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::write(&self.path, self.to_toml())
+            .with_context(|| format!("Failed to write answers to {}", self.path.display()))
+    }
+
+    fn check_part(&self, year: u32, day: u32, part: Part, result: &mut PartResult) -> bool {
+        let (Some(answer), Some(known)) = (&result.answer, self.get(year, day, part)) else {
+            return false;
+        };
+        if answer.to_string() != known {
+            result.status = Status::Regressed;
+            return true;
+        }
+        false
+    }
+
+    /// Compares results against the known answers, marking any that differ as
+    /// regressed. Returns a description of each regression.
+    pub fn check(&self, results: &mut [DayResult]) -> Vec<String> {
+        let mut regressions = Vec::new();
+        for result in results.iter_mut() {
+            let (year, day) = (result.year, result.day);
+            for (part, part_result) in [
+                (Part::One, &mut result.part1),
+                (Part::Two, &mut result.part2),
+            ] {
+                if self.check_part(year, day, part, part_result) {
+                    regressions.push(format!(
+                        "{year} day {day} part {part}: expected {}, got {}",
+                        self.get(year, day, part).unwrap_or_default(),
+                        part_result
+                            .answer
+                            .as_ref()
+                            .map(|a| a.to_string())
+                            .unwrap_or_default()
+                    ));
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = r#"[2023.day01]
+part1 = "142"
+part2 = 281
+
+[2023.day09]
+part1 = "-2"
+
+[2022.day01]
+part1 = "24000"
+"#;
+
+    #[test]
+    fn can_parse_answers() {
+        let answers = KnownAnswers::parse(ANSWERS).unwrap();
+        let get = |year, day, part| answers.get(&(year, day, part)).map(|s| s.as_str());
+        assert_eq!(get(2023, 1, Part::One), Some("142"));
+        assert_eq!(get(2023, 1, Part::Two), Some("281"));
+        assert_eq!(get(2023, 9, Part::One), Some("-2"));
+        assert_eq!(get(2023, 9, Part::Two), None);
+        assert_eq!(get(2022, 1, Part::One), Some("24000"));
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(KnownAnswers::parse("[day01]\npart1 = 1").is_err());
+        assert!(KnownAnswers::parse("[2023.one]\npart1 = 1").is_err());
+        assert!(KnownAnswers::parse("[2023.day01]\npart3 = 1").is_err());
+        assert!(KnownAnswers::parse("[2023.day01]\npart1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut known = KnownAnswers {
+            path: PathBuf::new(),
+            answers: KnownAnswers::parse(ANSWERS).unwrap(),
+        };
+        assert!(known.record(2023, 9, Part::Two, &Answer::from("ABC")));
+        assert!(!known.record(2023, 1, Part::One, &Answer::from(7usize)));
+        let answers = KnownAnswers::parse(&known.to_toml()).unwrap();
+        assert_eq!(answers, known.answers);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let known = KnownAnswers {
+            path: PathBuf::new(),
+            answers: KnownAnswers::parse(ANSWERS).unwrap(),
+        };
+        let mut results = vec![DayResult {
+            year: 2023,
+            day: 1,
+            input: None,
+            part1: PartResult {
+                answer: Some(Answer::from(142usize)),
+                status: Status::Done,
+                elapsed: 0.0,
+                memory: None,
+            },
+            part2: PartResult {
+                answer: Some(Answer::from(280usize)),
+                status: Status::Done,
+                elapsed: 0.0,
+                memory: None,
+            },
+            parse: 0.0,
+            parse_memory: None,
+            error: None,
+        }];
+        let regressions = known.check(&mut results);
+        assert_eq!(
+            regressions,
+            vec!["2023 day 1 part 2: expected 281, got 280"]
+        );
+        assert_eq!(results[0].part1.status, Status::Done);
+        assert_eq!(results[0].part2.status, Status::Regressed);
+    }
+}
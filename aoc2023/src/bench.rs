@@ -0,0 +1,503 @@
+use crate::cli::{Format, Part, Scheduling};
+use crate::runner::{self, DayResult, Status};
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+pub const DEFAULT_HISTORY: &str = "bench-history.jsonl";
+
+/// Summary statistics for a set of timings, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn of(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Stats {
+                min: 0.0,
+                median: 0.0,
+                stddev: 0.0,
+            };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    fn to_json(self) -> serde_json::Value {
+        json!({ "min": self.min, "median": self.median, "stddev": self.stddev })
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(Stats {
+            min: value.get("min")?.as_f64()?,
+            median: value.get("median")?.as_f64()?,
+            stddev: value.get("stddev")?.as_f64()?,
+        })
+    }
+}
+
+/// The stages of a day that get timed, in the order they're reported.
+pub const STAGES: [&str; 4] = ["parse", "part1", "part2", "total"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayBench {
+    pub year: u32,
+    pub day: u32,
+    /// Stats for each of [`STAGES`].
+    pub stages: [Stats; 4],
+}
+
+/// One `bench` run, as kept in the history file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub revision: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub runs: usize,
+    pub days: Vec<DayBench>,
+}
+
+impl Record {
+    fn to_json(&self) -> serde_json::Value {
+        let days: Vec<_> = self
+            .days
+            .iter()
+            .map(|d| {
+                let mut day = json!({ "year": d.year, "day": d.day });
+                for (stage, stats) in STAGES.iter().zip(d.stages) {
+                    day[*stage] = stats.to_json();
+                }
+                day
+            })
+            .collect();
+        json!({
+            "revision": self.revision,
+            "timestamp": self.timestamp.to_rfc3339(),
+            "runs": self.runs,
+            "days": days,
+        })
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let days = value
+            .get("days")?
+            .as_array()?
+            .iter()
+            .map(|d| {
+                let mut stages = [Stats::of(&[]); 4];
+                for (stats, stage) in stages.iter_mut().zip(STAGES) {
+                    *stats = Stats::from_json(d.get(stage)?)?;
+                }
+                Some(DayBench {
+                    year: d.get("year")?.as_u64()? as u32,
+                    day: d.get("day")?.as_u64()? as u32,
+                    stages,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let timestamp = chrono::DateTime::parse_from_rfc3339(value.get("timestamp")?.as_str()?)
+            .ok()?
+            .with_timezone(&chrono::Utc);
+        Some(Record {
+            revision: value.get("revision")?.as_str()?.to_string(),
+            timestamp,
+            runs: value.get("runs")?.as_u64()? as usize,
+            days,
+        })
+    }
+}
+
+/// Runs the given days `runs` times, leaving out days without an input or
+/// whose input doesn't parse.
+pub fn bench(
+    years: &[u32],
+    days: &[u32],
+    part: Option<Part>,
+    runs: usize,
+    scheduling: Scheduling,
+) -> Vec<DayBench> {
+    let runs = (0..runs)
+        .map(|_| runner::run_days(years, days, part, runner::DEFAULT_TIMEOUT, scheduling))
+        .collect::<Vec<_>>();
+    summarise_runs(&runs)
+}
+
+/// Gathers each day's timings across the runs. A day that fails in some runs
+/// (by timing out, say) only has the timings of the others.
+fn summarise_runs(runs: &[Vec<DayResult>]) -> Vec<DayBench> {
+    let mut samples: BTreeMap<(u32, u32), [Vec<f64>; 4]> = BTreeMap::new();
+    for r in runs.iter().flatten().filter(|r| has_input(r)) {
+        let timings = [r.parse, r.part1.elapsed, r.part2.elapsed, r.elapsed()];
+        let stages = samples.entry((r.year, r.day)).or_default();
+        for (stage, timing) in stages.iter_mut().zip(timings) {
+            stage.push(timing);
+        }
+    }
+    samples
+        .into_iter()
+        .map(|((year, day), stages)| DayBench {
+            year,
+            day,
+            stages: stages.map(|s| Stats::of(&s)),
+        })
+        .collect()
+}
+
+fn has_input(result: &DayResult) -> bool {
+    [&result.part1, &result.part2]
+        .iter()
+        .any(|p| matches!(p.status, Status::Done | Status::Pending))
+}
+
+/// The current git revision, marked if there are uncommitted changes.
+pub fn revision() -> String {
+    std::process::Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|revision| revision.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The most recent run in the history file, if there is one.
+pub fn load_previous(path: &Path) -> Result<Option<Record>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read bench history from {}", path.display()))?;
+    let Some(line) = contents.lines().rev().find(|l| !l.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let value: serde_json::Value = serde_json::from_str(line)
+        .with_context(|| format!("Invalid bench history in {}", path.display()))?;
+    Record::from_json(&value)
+        .map(Some)
+        .with_context(|| format!("Invalid bench history in {}", path.display()))
+}
+
+/// Adds a run to the end of the history file, one run per line.
+pub fn append(path: &Path, record: &Record) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open bench history at {}", path.display()))?;
+    writeln!(file, "{}", record.to_json())
+        .with_context(|| format!("Failed to write bench history to {}", path.display()))
+}
+
+/// A stage whose median time changed by more than the threshold between
+/// runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub year: u32,
+    pub day: u32,
+    pub stage: &'static str,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    pub fn percent(&self) -> f64 {
+        (self.after / self.before - 1.0) * 100.0
+    }
+
+    pub fn slower(&self) -> bool {
+        self.after > self.before
+    }
+}
+
+/// Compares the median of each stage against the previous run, keeping those
+/// that changed by more than `threshold` percent.
+pub fn compare(previous: &Record, current: &Record, threshold: f64) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for day in current.days.iter() {
+        let Some(before) = previous
+            .days
+            .iter()
+            .find(|d| d.year == day.year && d.day == day.day)
+        else {
+            continue;
+        };
+        for (i, stage) in STAGES.iter().enumerate() {
+            let (before, after) = (before.stages[i].median, day.stages[i].median);
+            // parts that weren't run (or solved) have nothing to compare
+            if before <= 0.0 || after <= 0.0 {
+                continue;
+            }
+            let change = Change {
+                year: day.year,
+                day: day.day,
+                stage,
+                before,
+                after,
+            };
+            if change.percent().abs() > threshold {
+                changes.push(change);
+            }
+        }
+    }
+    changes
+}
+
+fn stage_name(stage: &str) -> &'static str {
+    match stage {
+        "parse" => "Parse",
+        "part1" => "Part 1",
+        "part2" => "Part 2",
+        _ => "Total",
+    }
+}
+
+pub fn write_table<W: Write>(out: &mut W, days: &[DayBench], markdown: bool) -> Result<()> {
+    if markdown {
+        writeln!(out, "| Year | Day | Stage | Min | Median | Std Dev |")?;
+        writeln!(out, "|-----:|----:|:------|----:|-------:|--------:|")?;
+    } else {
+        writeln!(
+            out,
+            "{:>4} {:>3}  {:<6} {:>11} {:>11} {:>11}",
+            "Year", "Day", "Stage", "Min", "Median", "Std Dev"
+        )?;
+    }
+    for d in days.iter() {
+        for (stage, stats) in STAGES.iter().zip(d.stages) {
+            if stats.median <= 0.0 {
+                continue;
+            }
+            let (min, median, stddev) = (
+                format!("{:.6}s", stats.min),
+                format!("{:.6}s", stats.median),
+                format!("{:.6}s", stats.stddev),
+            );
+            if markdown {
+                writeln!(
+                    out,
+                    "| {} | {} | {} | {min} | {median} | {stddev} |",
+                    d.year,
+                    d.day,
+                    stage_name(stage)
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "{:>4} {:>3}  {:<6} {min:>11} {median:>11} {stddev:>11}",
+                    d.year,
+                    d.day,
+                    stage_name(stage)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn write_csv<W: Write>(out: &mut W, days: &[DayBench]) -> Result<()> {
+    writeln!(
+        out,
+        "year,day,stage,min_seconds,median_seconds,stddev_seconds"
+    )?;
+    for d in days.iter() {
+        for (stage, stats) in STAGES.iter().zip(d.stages) {
+            writeln!(
+                out,
+                "{},{},{stage},{:.6},{:.6},{:.6}",
+                d.year, d.day, stats.min, stats.median, stats.stddev
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print(record: &Record, format: Format) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &record.to_json())?;
+            writeln!(stdout)?;
+            Ok(())
+        }
+        Format::Csv => write_csv(&mut stdout, &record.days),
+        Format::Table => {
+            writeln!(stdout, "{} runs at {}:", record.runs, record.revision)?;
+            write_table(&mut stdout, &record.days, false)
+        }
+        Format::Markdown => write_table(&mut stdout, &record.days, true),
+    }
+}
+
+/// Prints how this run compares to the previous one, to stderr so it doesn't
+/// get mixed up with machine-readable output.
+pub fn print_changes(previous: &Record, changes: &[Change], threshold: f64) -> Result<()> {
+    let mut stderr = StandardStream::stderr(ColorChoice::Auto);
+    writeln!(
+        stderr,
+        "Compared to {} ({}):",
+        previous.revision,
+        previous.timestamp.format("%Y-%m-%d %H:%M")
+    )?;
+    if changes.is_empty() {
+        writeln!(stderr, "    no stage changed by more than {threshold}%")?;
+        return Ok(());
+    }
+    for change in changes.iter() {
+        let color = if change.slower() {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        write!(
+            stderr,
+            "    {} day {} {}: {:.6}s → {:.6}s ",
+            change.year,
+            change.day,
+            stage_name(change.stage),
+            change.before,
+            change.after
+        )?;
+        stderr.set_color(
+            ColorSpec::new()
+                .set_fg(Some(color))
+                .set_bold(change.slower()),
+        )?;
+        write!(stderr, "({:+.1}%)", change.percent())?;
+        stderr.reset()?;
+        writeln!(stderr)?;
+    }
+    let slower = changes.iter().filter(|c| c.slower()).count();
+    if slower > 0 {
+        writeln!(
+            stderr,
+            "{slower} stage(s) got more than {threshold}% slower"
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::PartResult;
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            min: median / 2.0,
+            median,
+            stddev: 0.001,
+        }
+    }
+
+    fn record(revision: &str, part1: f64) -> Record {
+        Record {
+            revision: revision.to_string(),
+            timestamp: chrono::DateTime::parse_from_rfc3339("2023-12-10T05:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+            runs: 10,
+            days: vec![DayBench {
+                year: 2023,
+                day: 5,
+                stages: [stats(0.5), stats(part1), stats(0.0), stats(0.5 + part1)],
+            }],
+        }
+    }
+
+    #[test]
+    fn can_compute_stats() {
+        let stats = Stats::of(&[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 2.5);
+        assert!((stats.stddev - 1.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!(Stats::of(&[3.0, 1.0, 2.0]).median, 2.0);
+        assert_eq!(Stats::of(&[]).median, 0.0);
+    }
+
+    #[test]
+    fn history_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        assert_eq!(load_previous(&path).unwrap(), None);
+
+        append(&path, &record("abc123", 1.0)).unwrap();
+        append(&path, &record("def456-dirty", 2.0)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        assert_eq!(
+            load_previous(&path).unwrap(),
+            Some(record("def456-dirty", 2.0))
+        );
+    }
+
+    #[test]
+    fn flags_slowdowns() {
+        let previous = record("abc123", 1.0);
+        assert!(compare(&previous, &record("abc124", 1.05), 10.0).is_empty());
+
+        let changes = compare(&previous, &record("abc124", 1.5), 10.0);
+        assert_eq!(
+            changes.iter().map(|c| c.stage).collect::<Vec<_>>(),
+            vec!["part1", "total"]
+        );
+        assert!(changes.iter().all(|c| c.slower()));
+        assert!((changes[0].percent() - 50.0).abs() < 1e-9);
+
+        let changes = compare(&previous, &record("abc124", 0.5), 10.0);
+        assert!(changes.iter().all(|c| !c.slower()));
+    }
+
+    #[test]
+    fn keeps_timings_with_their_days() {
+        let part = |status, elapsed| PartResult {
+            answer: None,
+            status,
+            elapsed,
+            memory: None,
+        };
+        let result = |day, status, elapsed| DayResult {
+            year: 2023,
+            day,
+            input: None,
+            part1: part(status, elapsed),
+            part2: part(Status::Skipped, 0.0),
+            parse: 0.0,
+            parse_memory: None,
+            error: None,
+        };
+        // day 1 timing out in the first run mustn't shift day 2's timings onto it
+        let runs = vec![
+            vec![
+                result(1, Status::TimedOut, 0.0),
+                result(2, Status::Done, 2.0),
+            ],
+            vec![result(1, Status::Done, 1.0), result(2, Status::Done, 2.0)],
+        ];
+        let days = summarise_runs(&runs);
+        assert_eq!(
+            days.iter()
+                .map(|d| (d.day, d.stages[1].median))
+                .collect::<Vec<_>>(),
+            vec![(1, 1.0), (2, 2.0)]
+        );
+    }
+}
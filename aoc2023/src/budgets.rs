@@ -0,0 +1,204 @@
+use crate::runner::DayResult;
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_PATH: &str = "budgets.toml";
+
+/// How long days are allowed to take, counting parsing and both parts.
+///
+/// These are stored in a TOML file with a budget for every day, one for all
+/// of them together, and overrides for individual days grouped by year:
+///
+/// ```toml
+/// day = "50ms"
+/// total = "1s"
+///
+/// [2023]
+/// day17 = "500ms"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Budgets {
+    day: Option<Duration>,
+    total: Option<Duration>,
+    days: BTreeMap<(u32, u32), Duration>,
+}
+
+/// Parses durations like `250us`, `50ms`, `1.5s` or `2m`.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let Ok(number) = number.parse::<f64>() else {
+        bail!("Invalid duration '{s}', expected something like '50ms'");
+    };
+    let seconds = match unit.trim() {
+        "us" | "µs" => number / 1_000_000.0,
+        "ms" => number / 1_000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => bail!("Invalid unit in duration '{s}', expected 'us', 'ms', 's' or 'm'"),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow!("Duration '{s}' is too long"))
+}
+
+fn parse_day_key(key: &str) -> Option<u32> {
+    key.strip_prefix("day")?.parse().ok()
+}
+
+fn budget(value: &toml::Value, name: &str) -> Result<Duration> {
+    match value {
+        toml::Value::String(s) => parse_duration(s),
+        _ => bail!("Budget for {name} must be a string like '50ms'"),
+    }
+}
+
+impl Budgets {
+    /// Loads the budgets file, treating a missing file as having no budgets.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read budgets from {}", path.display()))
+            }
+        };
+        Self::parse(&contents)
+            .with_context(|| format!("Failed to parse budgets from {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let table = contents.parse::<toml::Table>()?;
+        let mut budgets = Budgets::default();
+        for (key, value) in table.iter() {
+            match key.as_str() {
+                "day" => budgets.day = Some(budget(value, "every day")?),
+                "total" => budgets.total = Some(budget(value, "all days")?),
+                _ => {
+                    let Ok(year) = key.parse::<u32>() else {
+                        bail!("Invalid key '{key}', expected 'day', 'total' or a year");
+                    };
+                    let Some(days) = value.as_table() else {
+                        bail!("Expected '{key}' to be a table");
+                    };
+                    for (key, value) in days.iter() {
+                        let Some(day) = parse_day_key(key) else {
+                            bail!("Invalid day '{key}', expected something like 'day01'");
+                        };
+                        let budget = budget(value, &format!("{year} day {day}"))?;
+                        budgets.days.insert((year, day), budget);
+                    }
+                }
+            }
+        }
+        Ok(budgets)
+    }
+
+    pub fn for_day(&self, year: u32, day: u32) -> Option<Duration> {
+        self.days.get(&(year, day)).copied().or(self.day)
+    }
+
+    /// Checks the results against their budgets, returning a description of
+    /// each one that was exceeded.
+    pub fn check(&self, results: &[DayResult]) -> Vec<String> {
+        let mut exceeded = Vec::new();
+        for result in results.iter() {
+            let Some(budget) = self.for_day(result.year, result.day) else {
+                continue;
+            };
+            if result.elapsed() > budget.as_secs_f64() {
+                exceeded.push(format!(
+                    "{} day {}: took {:.6}s, over its {:?} budget",
+                    result.year,
+                    result.day,
+                    result.elapsed(),
+                    budget
+                ));
+            }
+        }
+        if let Some(budget) = self.total {
+            let total: f64 = results.iter().map(|r| r.elapsed()).sum();
+            if total > budget.as_secs_f64() {
+                exceeded.push(format!(
+                    "all days: took {total:.6}s, over the {budget:?} budget"
+                ));
+            }
+        }
+        exceeded
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::{PartResult, Status};
+
+    fn result(day: u32, elapsed: f64) -> DayResult {
+        let part = |elapsed| PartResult {
+            answer: None,
+            status: Status::Pending,
+            elapsed,
+            memory: None,
+        };
+        DayResult {
+            year: 2023,
+            day,
+            input: None,
+            part1: part(elapsed / 2.0),
+            part2: part(elapsed / 2.0),
+            parse: 0.0,
+            parse_memory: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn can_parse_durations() {
+        assert_eq!(parse_duration("250us").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("50ms").unwrap(), Duration::from_millis(50));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_duration("50").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("100000000000000000000s").is_err());
+        assert!(parse_duration(&format!("{}m", "9".repeat(400))).is_err());
+    }
+
+    #[test]
+    fn can_parse_budgets() {
+        let budgets =
+            Budgets::parse("day = \"50ms\"\ntotal = \"1s\"\n\n[2023]\nday17 = \"500ms\"\n")
+                .unwrap();
+        assert_eq!(budgets.for_day(2023, 1), Some(Duration::from_millis(50)));
+        assert_eq!(budgets.for_day(2023, 17), Some(Duration::from_millis(500)));
+        assert_eq!(budgets.total, Some(Duration::from_secs(1)));
+        assert_eq!(Budgets::parse("").unwrap(), Budgets::default());
+
+        assert!(Budgets::parse("day = 50\n").is_err());
+        assert!(Budgets::parse("days = \"50ms\"\n").is_err());
+        assert!(Budgets::parse("[2023]\n17 = \"50ms\"\n").is_err());
+    }
+
+    #[test]
+    fn flags_exceeded_budgets() {
+        let budgets =
+            Budgets::parse("day = \"50ms\"\ntotal = \"1s\"\n\n[2023]\nday17 = \"800ms\"\n")
+                .unwrap();
+        assert!(budgets
+            .check(&[result(1, 0.01), result(17, 0.7)])
+            .is_empty());
+
+        let exceeded = budgets.check(&[result(1, 0.06), result(17, 0.7), result(18, 0.3)]);
+        assert_eq!(exceeded.len(), 3, "{exceeded:?}");
+        assert!(exceeded[0].starts_with("2023 day 1: took 0.060000s"));
+        assert!(exceeded[1].starts_with("2023 day 18: took 0.300000s"));
+        assert!(exceeded[2].starts_with("all days: took 1.060000s"));
+
+        assert!(Budgets::default().check(&[result(1, 10.0)]).is_empty());
+    }
+}
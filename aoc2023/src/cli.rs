@@ -0,0 +1,495 @@
+use crate::{answers, bench, budgets};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::builder::BoolishValueParser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2023 solutions", version)]
+#[command(subcommand_precedence_over_arg = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub run: RunArgs,
+
+    /// Which years to use, e.g. `2023` or `2022,2023` (default: the latest
+    /// year with solutions)
+    #[arg(
+        long = "year",
+        global = true,
+        value_delimiter = ',',
+        env = "AOC_YEAR",
+        value_parser = clap::value_parser!(u32).range(2015..)
+    )]
+    pub years: Vec<u32>,
+
+    #[command(flatten)]
+    pub headless: Headless,
+}
+
+/// How days are scheduled while they're being timed.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct Scheduling {
+    /// Run days one at a time rather than in parallel, so that they don't
+    /// compete for cores and timings can be compared between runs
+    #[arg(long)]
+    pub sequential: bool,
+
+    /// How many threads to run on, including for days that parallelise
+    /// internally (1 runs everything single-threaded; default: one per core)
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+}
+
+/// Options for running without anyone at the keyboard, e.g. in CI.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct Headless {
+    /// Answer yes to any prompts, such as downloading missing inputs
+    #[arg(
+        short = 'y',
+        long,
+        global = true,
+        env = "AOC_YES",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub yes: bool,
+
+    /// Never download missing inputs; days without one are skipped
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_NO_DOWNLOAD",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub no_download: bool,
+
+    /// Don't make any requests to Advent of Code
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_OFFLINE",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub offline: bool,
+}
+
+impl Headless {
+    /// Whether missing inputs may be downloaded automatically.
+    pub fn can_download(&self) -> bool {
+        !self.offline && !self.no_download
+    }
+
+    /// Whether we can stop and ask the user something.
+    pub fn can_prompt(&self) -> bool {
+        use std::io::IsTerminal;
+        !self.yes && std::io::stdin().is_terminal()
+    }
+}
+
+impl Cli {
+    /// Parses the command line, exiting with usage if it's invalid.
+    pub fn parse_args() -> Self {
+        Self::try_parse_args(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+
+    /// Like [`Parser::try_parse_from`], but rejects `run`'s options when
+    /// they're given before a subcommand, rather than silently ignoring them.
+    pub fn try_parse_args<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        let cli = Self::from_arg_matches(&matches)?;
+        if let Some((name, _)) = matches.subcommand() {
+            let mut run = RunArgs::augment_args(clap::Command::new("run"));
+            run.build();
+            let given = run.get_arguments().find(|arg| {
+                matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            });
+            if let Some(arg) = given {
+                let message = format!("'{arg}' cannot be used before the '{name}' subcommand");
+                return Err(command.error(ErrorKind::ArgumentConflict, message));
+            }
+        }
+        Ok(cli)
+    }
+
+    pub fn years(&self) -> Vec<u32> {
+        if !self.years.is_empty() {
+            let mut years = self.years.clone();
+            years.sort();
+            years.dedup();
+            return years;
+        }
+        aoc2023::years().last().copied().into_iter().collect()
+    }
+
+    /// Running the binary without a subcommand is the same as `run`.
+    pub fn into_command(self) -> Command {
+        self.command.unwrap_or(Command::Run(self.run))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run solutions against their inputs and print the results table
+    Run(RunArgs),
+    /// Download any missing inputs
+    Download {
+        /// Days to download, e.g. `5`, `5..10` or `3,7,19` (default: all)
+        days: Option<Days>,
+
+        /// Also re-download inputs that look corrupted
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Submit an answer to Advent of Code
+    Submit {
+        /// The day to submit an answer for
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Which part the answer is for
+        part: Part,
+
+        /// The answer to submit (default: run the solution and submit its
+        /// answer)
+        answer: Option<String>,
+    },
+    /// Time solutions over several runs, and compare them to the last time
+    Bench(BenchArgs),
+    /// Check the given days against the examples from their puzzle descriptions
+    Test {
+        /// Days to test, e.g. `5`, `5..10` or `3,7,19` (default: all)
+        days: Option<Days>,
+
+        /// Give up on a sample that's still running after this long, e.g. `30s`
+        #[arg(long, default_value = "1m", value_parser = budgets::parse_duration)]
+        timeout: Duration,
+    },
+    /// Read a puzzle's description, downloading it if needed
+    Read {
+        /// The day to read
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Print it as Markdown instead of paging it
+        #[arg(long)]
+        markdown: bool,
+
+        /// Download it again, e.g. to pick up part 2
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Generate the skeleton for a new day, leaving any existing files alone
+    New {
+        /// The day to generate
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Re-run a day whenever its source or input changes
+    Watch {
+        /// The day to watch
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Show a private leaderboard
+    Leaderboard {
+        /// The leaderboard's id, from the end of its URL
+        #[arg(env = "AOC_LEADERBOARD")]
+        id: String,
+
+        /// Show when each member earned their stars for this day instead
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: Option<u32>,
+    },
+    /// Wait for a puzzle to unlock, then download it and get started on it
+    Wait {
+        /// The day to wait for (default: the next one to unlock)
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: Option<u32>,
+    },
+    /// List the registered days
+    List,
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Days to run, e.g. `5`, `5..10` or `3,7,19` (default: all)
+    pub days: Option<Days>,
+
+    /// Only run one part
+    #[arg(short, long)]
+    pub part: Option<Part>,
+
+    /// Run against these input files instead of `inputs/dayNN.txt`, with `-`
+    /// reading from stdin (requires a single day)
+    #[arg(short, long, num_args = 1..)]
+    pub input: Vec<PathBuf>,
+
+    /// Run every input in `inputs/YYYY/dayNN/` instead, checking them against
+    /// the answers in that directory's `answers.toml`
+    #[arg(long, conflicts_with = "input")]
+    pub all_inputs: bool,
+
+    /// File of known answers to check results against
+    #[arg(long, default_value = answers::DEFAULT_PATH)]
+    pub answers: PathBuf,
+
+    /// Record answers for any parts that don't have a known answer yet
+    #[arg(long)]
+    pub record: bool,
+
+    /// File of time budgets to hold days to
+    #[arg(long, default_value = budgets::DEFAULT_PATH)]
+    pub budgets: PathBuf,
+
+    /// Give up on a day that's still running after this long, e.g. `30s`
+    #[arg(long, default_value = "1m", value_parser = budgets::parse_duration)]
+    pub timeout: Duration,
+
+    #[command(flatten)]
+    pub scheduling: Scheduling,
+
+    /// Also show each stage's peak heap usage and number of allocations
+    /// (implies --sequential, since the counts are shared between days)
+    #[arg(long)]
+    pub memory: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Days to bench, e.g. `5`, `5..10` or `3,7,19` (default: all)
+    pub days: Option<Days>,
+
+    /// Only bench one part
+    #[arg(short, long)]
+    pub part: Option<Part>,
+
+    /// How many times to run each day
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub runs: usize,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+
+    /// The file to keep past results in, to compare against
+    #[arg(long, default_value = bench::DEFAULT_HISTORY)]
+    pub history: PathBuf,
+
+    /// How much slower (in percent) a stage has to get to be flagged
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Bench days in parallel. It's quicker, but they compete for cores, so
+    /// their timings are noisier and harder to compare between runs
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// How many threads to run on, for days that parallelise internally (1
+    /// runs everything single-threaded; default: one per core)
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+}
+
+impl BenchArgs {
+    /// Unlike `run`, days are benched one at a time unless asked otherwise.
+    pub fn scheduling(&self) -> Scheduling {
+        Scheduling {
+            sequential: !self.parallel,
+            threads: self.threads,
+        }
+    }
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A set of days, parsed from a comma-separated list of days and inclusive
+/// ranges such as `1,3..5,9`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Days(Vec<u32>);
+
+impl Days {
+    pub fn all() -> Self {
+        Days((1..=25).collect())
+    }
+
+    pub fn or_all(days: Option<Days>) -> Vec<u32> {
+        days.unwrap_or_else(Days::all).0
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+    let day = s
+        .trim()
+        .parse::<u32>()
+        .with_context(|| format!("Invalid day: '{s}'"))?;
+    if !(1..=25).contains(&day) {
+        bail!("Day {day} is out of range (1-25)");
+    }
+    Ok(day)
+}
+
+impl FromStr for Days {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut days = Vec::new();
+        for item in s.split(',') {
+            if let Some((start, end)) = item.split_once("..") {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(anyhow!("Invalid range: '{item}'"));
+                }
+                days.extend(start..=end);
+            } else {
+                days.push(parse_day(item)?);
+            }
+        }
+        days.sort();
+        days.dedup();
+        Ok(Days(days))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn can_parse_days() {
+        assert_eq!("5".parse::<Days>().unwrap(), Days(vec![5]));
+        assert_eq!("5..8".parse::<Days>().unwrap(), Days(vec![5, 6, 7, 8]));
+        assert_eq!("5..=8".parse::<Days>().unwrap(), Days(vec![5, 6, 7, 8]));
+        assert_eq!("3,7,19".parse::<Days>().unwrap(), Days(vec![3, 7, 19]));
+        assert_eq!("9,1..3,2".parse::<Days>().unwrap(), Days(vec![1, 2, 3, 9]));
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("8..5".parse::<Days>().is_err());
+        assert!("five".parse::<Days>().is_err());
+    }
+
+    #[test]
+    fn can_parse_years() {
+        let cli = Cli::try_parse_from(["aoc2023", "--year", "2023,2022", "5"]).unwrap();
+        assert_eq!(cli.years(), vec![2022, 2023]);
+        let cli = Cli::try_parse_from(["aoc2023", "list", "--year", "2021"]).unwrap();
+        assert_eq!(cli.years(), vec![2021]);
+        assert!(Cli::try_parse_from(["aoc2023", "--year", "2014"]).is_err());
+    }
+
+    #[test]
+    fn global_flags_can_come_before_a_subcommand() {
+        let cli = Cli::try_parse_args(["aoc2023", "--year", "2022", "list"]).unwrap();
+        assert_eq!(cli.years(), vec![2022]);
+        assert!(matches!(cli.into_command(), Command::List));
+
+        let cli = Cli::try_parse_args(["aoc2023", "-y", "list"]).unwrap();
+        assert!(cli.headless.yes);
+        assert!(matches!(cli.into_command(), Command::List));
+
+        let cli = Cli::try_parse_args(["aoc2023", "--offline", "new", "12"]).unwrap();
+        assert!(cli.headless.offline);
+        assert!(matches!(cli.into_command(), Command::New { day: 12 }));
+    }
+
+    #[test]
+    fn run_options_cannot_come_before_a_subcommand() {
+        assert!(Cli::try_parse_args(["aoc2023", "9", "list"]).is_err());
+        assert!(Cli::try_parse_args(["aoc2023", "--part", "1", "run", "9"]).is_err());
+        assert!(Cli::try_parse_args(["aoc2023", "--input", "a", "b", "list"]).is_err());
+
+        let cli = Cli::try_parse_args(["aoc2023", "--year", "2023", "9"]).unwrap();
+        assert!(matches!(
+            cli.into_command(),
+            Command::Run(RunArgs { days: Some(_), .. })
+        ));
+    }
+
+    #[test]
+    fn bench_is_sequential_unless_asked() {
+        let bench = |args: &[&str]| {
+            let cli = Cli::try_parse_args(["aoc2023", "bench"].iter().chain(args)).unwrap();
+            match cli.into_command() {
+                Command::Bench(args) => args.scheduling(),
+                command => panic!("expected bench, got {command:?}"),
+            }
+        };
+        assert!(bench(&[]).sequential);
+        assert!(!bench(&["--parallel"]).sequential);
+        assert_eq!(bench(&["--threads", "2"]).threads, Some(2));
+        assert!(Cli::try_parse_args(["aoc2023", "bench", "--sequential"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_headless_flags() {
+        let cli = Cli::try_parse_from(["aoc2023", "run", "-y", "--offline"]).unwrap();
+        assert!(cli.headless.yes);
+        assert!(cli.headless.offline);
+        assert!(!cli.headless.can_download());
+        assert!(!cli.headless.can_prompt());
+
+        let cli = Cli::try_parse_from(["aoc2023", "--no-download", "1"]).unwrap();
+        assert!(cli.headless.no_download);
+        assert!(!cli.headless.can_download());
+    }
+
+    #[test]
+    fn can_set_headless_flags_from_the_environment() {
+        // the only test to touch these, and it only sets them
+        std::env::set_var("AOC_YES", "1");
+        std::env::set_var("AOC_OFFLINE", "0");
+        let cli = Cli::try_parse_from(["aoc2023", "list"]).unwrap();
+        assert!(cli.headless.yes);
+        assert!(!cli.headless.offline);
+    }
+
+    #[test]
+    fn cli_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+}
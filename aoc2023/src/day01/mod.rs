@@ -1,3 +1,5 @@
+use crate::{Answer, ParseError, Solution};
+
 fn is_digit(c: &char) -> bool {
     *c >= '0' && *c <= '9'
 }
@@ -72,8 +74,34 @@ pub fn part2(input: &str) -> usize {
         .sum()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'i> = &'i str;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn parse<'i>(&self, input: &'i str) -> Result<&'i str, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(&self, input: &&str) -> Option<Answer> {
+        Some(part1(input).into())
+    }
+
+    fn part2(&self, input: &&str) -> Option<Answer> {
+        Some(part2(input).into())
+    }
 }
 
 #[cfg(test)]
@@ -1,59 +1,56 @@
-struct Game {
+use crate::{parse_number, Answer, ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Game {
     id: usize,
     rounds: Vec<(usize, usize, usize)>,
 }
 
-fn is_digit(c: &char) -> bool {
-    *c >= '0' && *c <= '9'
-}
-
-fn extract_and_parse_numbers(s: &str) -> Option<usize> {
-    s.chars()
-        .filter(is_digit)
-        .collect::<String>()
-        .parse::<usize>()
-        .ok()
-}
-
-fn parse_round(round: &str) -> (usize, usize, usize) {
-    let parts = round.split(',').collect::<Vec<&str>>();
+fn parse_round(input: &str, round: &str) -> Result<(usize, usize, usize), ParseError> {
     let mut r = 0;
     let mut g = 0;
     let mut b = 0;
 
-    for part in parts {
-        let mut pull = part.trim().split_whitespace();
-        let count = pull.next();
-        let colour = pull.next();
-
-        match (count, colour) {
-            (Some(count), Some(colour)) if colour.len() > 0 => {
-                let count = extract_and_parse_numbers(count);
-                if let Some(count) = count {
-                    match colour.chars().next().unwrap() {
-                        'r' => r += count,
-                        'g' => g += count,
-                        'b' => b += count,
-                        _ => {}
-                    }
-                }
+    for part in round.split(',') {
+        let mut pull = part.split_whitespace();
+        let (Some(count), Some(colour)) = (pull.next(), pull.next()) else {
+            let at = part.trim_start();
+            return Err(ParseError::at(input, at, "expected a count and a colour"));
+        };
+        let count = parse_number::<usize>(input, count)?;
+        match colour {
+            "red" => r += count,
+            "green" => g += count,
+            "blue" => b += count,
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    colour,
+                    format!("expected red, green or blue, found '{colour}'"),
+                ))
             }
-            _ => {}
         }
     }
 
-    (r, g, b)
+    Ok((r, g, b))
 }
 
-fn parse_game(line: &str) -> Option<Game> {
+fn parse_game(input: &str, line: &str) -> Result<Game, ParseError> {
     // parse the game #
-    let mut parts = line.splitn(2, ':');
-    let title = parts.next()?;
-    let id = extract_and_parse_numbers(title)?;
-
-    let rounds: Vec<_> = parts.next()?.split(';').map(parse_round).collect();
-
-    Some(Game { id, rounds })
+    let Some((title, rounds)) = line.split_once(':') else {
+        return Err(ParseError::at(input, line, "expected 'Game N: ...'"));
+    };
+    let Some(id) = title.strip_prefix("Game ") else {
+        return Err(ParseError::at(input, title, "expected 'Game N'"));
+    };
+    let id = parse_number(input, id)?;
+
+    let rounds = rounds
+        .split(';')
+        .map(|round| parse_round(input, round))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Game { id, rounds })
 }
 
 fn is_game_possible(game: &Game) -> bool {
@@ -62,16 +59,23 @@ fn is_game_possible(game: &Game) -> bool {
         .all(|g| g.0 <= 12 && g.1 <= 13 && g.2 <= 14)
 }
 
-pub fn part1(input: &str) -> usize {
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
     input
         .lines()
-        .filter_map(parse_game)
-        .filter(is_game_possible)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_game(input, line))
+        .collect()
+}
+
+pub fn part1(games: &[Game]) -> usize {
+    games
+        .iter()
+        .filter(|g| is_game_possible(g))
         .map(|g| g.id)
         .sum()
 }
 
-fn min_cubes(game: Game) -> (usize, usize, usize) {
+fn min_cubes(game: &Game) -> (usize, usize, usize) {
     game.rounds.iter().fold((0, 0, 0), |acc, el| {
         (acc.0.max(el.0), acc.1.max(el.1), acc.2.max(el.2))
     })
@@ -81,17 +85,38 @@ fn power(game: (usize, usize, usize)) -> usize {
     game.0 * game.1 * game.2
 }
 
-pub fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .filter_map(parse_game)
-        .map(min_cubes)
-        .map(power)
-        .sum()
+pub fn part2(games: &[Game]) -> usize {
+    games.iter().map(min_cubes).map(power).sum()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'i> = Vec<Game>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, games: &Vec<Game>) -> Option<Answer> {
+        Some(part1(games).into())
+    }
+
+    fn part2(&self, games: &Vec<Game>) -> Option<Answer> {
+        Some(part2(games).into())
+    }
 }
 
 #[cfg(test)]
@@ -104,11 +129,21 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
 
     #[test]
     fn day02_sample_part1() {
-        assert_eq!(super::part1(SAMPLE), 8);
+        assert_eq!(super::part1(&super::parse(SAMPLE).unwrap()), 8);
     }
 
     #[test]
     fn day02_sample_part2() {
-        assert_eq!(super::part2(SAMPLE), 2286);
+        assert_eq!(super::part2(&super::parse(SAMPLE).unwrap()), 2286);
+    }
+
+    #[test]
+    fn day02_points_at_bad_input() {
+        let error = super::parse("Game 1: 3 blue\nGame 2: 1 purple, 2 green").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.text, "Game 2: 1 purple, 2 green");
+
+        let error = super::parse("Game 1: 3 blue; x red").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 17));
     }
 }
@@ -1,3 +1,5 @@
+use crate::{parse_number, Answer, ParseError, Solution};
+
 fn is_symbol(c: char) -> bool {
     c != '.' && !c.is_ascii_digit() && !c.is_ascii_whitespace()
 }
@@ -14,8 +16,15 @@ fn is_adjacent((sx, sy): (usize, usize), (nx1, ny): (usize, usize), xlen: usize)
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let symbol_locations = input
+/// The symbols in the schematic, along with every number's location, value
+/// and length.
+pub struct Schematic {
+    symbols: Vec<((usize, usize), char)>,
+    numbers: Vec<((usize, usize), usize, usize)>,
+}
+
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let symbols = input
         .lines()
         .enumerate()
         .flat_map(|(y, line)| {
@@ -30,21 +39,25 @@ pub fn part1(input: &str) -> usize {
         .collect::<Vec<((usize, usize), char)>>();
 
     let re = regex::Regex::new(r"\d+").unwrap();
-    let mut numbers: Vec<((usize, usize), &str)> = Vec::default();
+    let mut numbers: Vec<((usize, usize), usize, usize)> = Vec::default();
     for (y, line) in input.lines().enumerate() {
         for number in re.find_iter(line) {
             let (x, e) = (number.start(), number.end());
-            let number = &line[x..e];
-            numbers.push(((x, y), number));
+            let number = parse_number(input, &line[x..e])?;
+            numbers.push(((x, y), number, e - x));
         }
     }
 
+    Ok(Schematic { symbols, numbers })
+}
+
+pub fn part1(schematic: &Schematic) -> usize {
     // if its dumb but it works...
     let mut sum: usize = 0;
-    for symbol in symbol_locations.iter() {
-        for number in numbers.iter() {
-            if is_adjacent(symbol.0, number.0, number.1.len()) {
-                sum += number.1.parse::<usize>().unwrap();
+    for symbol in schematic.symbols.iter() {
+        for number in schematic.numbers.iter() {
+            if is_adjacent(symbol.0, number.0, number.2) {
+                sum += number.1;
             }
         }
     }
@@ -56,37 +69,17 @@ fn is_gear(c: char) -> bool {
     c == '*'
 }
 
-pub fn part2(input: &str) -> usize {
-    let symbol_locations = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(move |(x, c)| if is_gear(c) { Some(((x, y), c)) } else { None })
-        })
-        .collect::<Vec<((usize, usize), char)>>();
-
-    let re = regex::Regex::new(r"\d+").unwrap();
-    let mut numbers: Vec<((usize, usize), &str)> = Vec::default();
-    for (y, line) in input.lines().enumerate() {
-        for number in re.find_iter(line) {
-            let (x, e) = (number.start(), number.end());
-            let number = &line[x..e];
-            numbers.push(((x, y), number));
-        }
-    }
-
+pub fn part2(schematic: &Schematic) -> usize {
     let mut sum: usize = 0;
-    for symbol in symbol_locations.iter() {
-        let adjacent_numbers = numbers
+    for symbol in schematic.symbols.iter().filter(|s| is_gear(s.1)) {
+        let adjacent_numbers = schematic
+            .numbers
             .iter()
-            .filter(|number| is_adjacent(symbol.0, number.0, number.1.len()))
+            .filter(|number| is_adjacent(symbol.0, number.0, number.2))
             .take(3)
-            .collect::<Vec<&((usize, usize), &str)>>();
+            .collect::<Vec<&((usize, usize), usize, usize)>>();
         if adjacent_numbers.len() == 2 {
-            let gear_ratio = adjacent_numbers[0].1.parse::<usize>().unwrap()
-                * adjacent_numbers[1].1.parse::<usize>().unwrap();
+            let gear_ratio = adjacent_numbers[0].1 * adjacent_numbers[1].1;
             sum += gear_ratio;
         }
     }
@@ -94,8 +87,34 @@ pub fn part2(input: &str) -> usize {
     sum
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'i> = Schematic;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn parse(&self, input: &str) -> Result<Schematic, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, schematic: &Schematic) -> Option<Answer> {
+        Some(part1(schematic).into())
+    }
+
+    fn part2(&self, schematic: &Schematic) -> Option<Answer> {
+        Some(part2(schematic).into())
+    }
 }
 
 #[cfg(test)]
@@ -115,11 +134,11 @@ mod test {
 
     #[test]
     fn day03_sample_part1() {
-        assert_eq!(part1(SAMPLE), 4361);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 4361);
     }
 
     #[test]
     fn day03_sample_part2() {
-        assert_eq!(part2(SAMPLE), 467835);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 467835);
     }
 }
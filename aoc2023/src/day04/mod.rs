@@ -1,62 +1,71 @@
-pub fn part1(input: &str) -> usize {
-    input
+use crate::{parse_number, Answer, ParseError, Solution};
+
+fn parse_numbers(input: &str, numbers: &str) -> Result<Vec<u32>, ParseError> {
+    numbers
+        .split_whitespace()
+        .map(|n| parse_number(input, n))
+        .collect()
+}
+
+/// Parses each card down to how many of its numbers are winning numbers.
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let lines: Vec<&str> = input
         .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let matches = lines
+        .iter()
         .map(|line| {
-            let mut parts = line.splitn(2, '|');
-            let winning = parts.next().expect("winning half");
-            let numbers = parts.next().expect("numbers half");
-
-            let winning_numbers = winning
-                .splitn(2, ':')
-                .skip(1)
-                .next()
-                .expect("list of winning numbers");
-            let winning_numbers = winning_numbers
-                .trim()
-                .split_whitespace()
-                .collect::<Vec<&str>>();
-
-            let matching_numbers = numbers
-                .trim()
-                .split_whitespace()
+            let Some((_, card)) = line.split_once(':') else {
+                return Err(ParseError::at(input, line, "expected 'Card N: ...'"));
+            };
+            let Some((winning, numbers)) = card.split_once('|') else {
+                return Err(ParseError::at(
+                    input,
+                    card,
+                    "expected winning numbers and your numbers separated by '|'",
+                ));
+            };
+
+            let winning_numbers = parse_numbers(input, winning)?;
+            let matching_numbers = parse_numbers(input, numbers)?
+                .into_iter()
                 .filter(|n| winning_numbers.contains(n));
+            Ok(matching_numbers.count())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-            match matching_numbers.count() {
-                0 => 0,
-                n => 2_usize.pow(n as u32 - 1),
-            }
+    // part 2 would win copies of cards that aren't there
+    let past_the_end = (0..matches.len()).find(|&i| i + matches[i] >= matches.len());
+    if let Some(i) = past_the_end {
+        return Err(ParseError::at(
+            input,
+            lines[i],
+            format!(
+                "wins copies of the next {} cards, but there are only {} after it",
+                matches[i],
+                matches.len() - i - 1
+            ),
+        ));
+    }
+    Ok(matches)
+}
+
+pub fn part1(matches: &[usize]) -> usize {
+    matches
+        .iter()
+        .map(|&n| match n {
+            0 => 0,
+            n => 2_usize.pow(n as u32 - 1),
         })
         .sum()
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut lines: Vec<(&str, usize)> = Vec::default();
-    for line in input.lines() {
-        let mut parts = line.splitn(2, '|');
-        let winning = parts.next().expect("winning half");
-        let numbers = parts.next().expect("numbers half");
-
-        let winning_numbers = winning
-            .splitn(2, ':')
-            .skip(1)
-            .next()
-            .expect("list of winning numbers");
-        let winning_numbers = winning_numbers
-            .trim()
-            .split_whitespace()
-            .collect::<Vec<&str>>();
-
-        let matching_numbers = numbers
-            .trim()
-            .split_whitespace()
-            .filter(|n| winning_numbers.contains(n));
-        lines.push((line, matching_numbers.count()));
-    }
-
-    let mut card_counts = vec![1usize; lines.len()];
-    for i in 0..lines.len() {
+pub fn part2(matches: &[usize]) -> usize {
+    let mut card_counts = vec![1usize; matches.len()];
+    for i in 0..matches.len() {
         for _ in 0..card_counts[i] {
-            for j in 0..lines[i].1 {
+            for j in 0..matches[i] {
                 card_counts[i + 1 + j] += 1;
             }
         }
@@ -65,8 +74,34 @@ pub fn part2(input: &str) -> usize {
     card_counts.into_iter().sum()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'i> = Vec<usize>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, matches: &Vec<usize>) -> Option<Answer> {
+        Some(part1(matches).into())
+    }
+
+    fn part2(&self, matches: &Vec<usize>) -> Option<Answer> {
+        Some(part2(matches).into())
+    }
 }
 
 #[cfg(test)]
@@ -82,11 +117,21 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
 
     #[test]
     fn day04_sample_part1() {
-        assert_eq!(part1(SAMPLE), 13);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 13);
     }
 
     #[test]
     fn day04_sample_part2() {
-        assert_eq!(part2(SAMPLE), 30);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 30);
+    }
+
+    #[test]
+    fn day04_rejects_winning_cards_past_the_end() {
+        let error = parse("Card 1: 41 48 | 48 1\nCard 2: 1 2 | 1 2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.message,
+            "wins copies of the next 2 cards, but there are only 0 after it"
+        );
     }
 }
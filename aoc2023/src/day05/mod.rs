@@ -1,3 +1,5 @@
+use crate::{parse_number, Answer, ParseError, Solution};
+
 #[derive(Debug)]
 struct Map {
     destination: usize,
@@ -5,21 +7,23 @@ struct Map {
     length: usize,
 }
 
-impl From<&str> for Map {
-    fn from(s: &str) -> Self {
-        let mut parts = s.split(" ");
-        let destination = parts.next().unwrap().parse::<usize>().unwrap();
-        let source = parts.next().unwrap().parse::<usize>().unwrap();
-        let length = parts.next().unwrap().parse::<usize>().unwrap();
-        Self {
-            destination,
-            source,
-            length,
-        }
+impl Map {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        let [destination, source, length] = parts[..] else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected a destination, source and length",
+            ));
+        };
+        Ok(Self {
+            destination: parse_number(input, destination)?,
+            source: parse_number(input, source)?,
+            length: parse_number(input, length)?,
+        })
     }
-}
 
-impl Map {
     fn map(&self, x: usize) -> Option<usize> {
         if x >= self.source && x <= self.source + self.length {
             let res = self.destination + (x - self.source);
@@ -38,17 +42,24 @@ fn map(maps: &Vec<Map>, x: usize) -> usize {
     }
 }
 
-pub fn part1(input: &str) -> usize {
+pub struct Almanac {
+    seeds: Vec<usize>,
+    maps: Vec<Vec<Map>>,
+}
+
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
     let mut lines = input.lines();
-    let seeds = lines
-        .next()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split(" ")
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
+    let first = lines.next().unwrap_or_default();
+    let Some(seeds) = first.strip_prefix("seeds:") else {
+        return Err(ParseError::at(input, first, "expected 'seeds: ...'"));
+    };
+    let seeds = seeds
+        .split_whitespace()
+        .map(|s| parse_number(input, s))
+        .collect::<Result<Vec<_>, _>>()?;
+    if seeds.is_empty() {
+        return Err(ParseError::at(input, first, "expected at least one seed"));
+    }
 
     let mut maps: Vec<Vec<Map>> = Vec::default();
     let mut current_map: Vec<Map> = Vec::default();
@@ -61,16 +72,21 @@ pub fn part1(input: &str) -> usize {
             current_map = Vec::default();
             continue;
         }
-        let map = Map::from(line.trim());
+        let map = Map::parse(input, line.trim())?;
         current_map.push(map);
     }
     maps.push(current_map);
 
-    seeds
+    Ok(Almanac { seeds, maps })
+}
+
+pub fn part1(almanac: &Almanac) -> usize {
+    almanac
+        .seeds
         .iter()
         .map(|seed| {
             let mut x: usize = *seed;
-            for mapping in maps.iter() {
+            for mapping in almanac.maps.iter() {
                 x = map(mapping, x);
             }
             x
@@ -79,8 +95,34 @@ pub fn part1(input: &str) -> usize {
         .expect("min value")
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), None)
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input<'i> = Almanac;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn parse(&self, input: &str) -> Result<Almanac, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, almanac: &Almanac) -> Option<Answer> {
+        Some(part1(almanac).into())
+    }
+
+    fn part2(&self, _almanac: &Almanac) -> Option<Answer> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +166,13 @@ humidity-to-location map:
 
     #[test]
     fn day05_sample_part1() {
-        assert_eq!(part1(SAMPLE), 35);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 35);
+    }
+
+    #[test]
+    fn day05_rejects_no_seeds() {
+        let error = parse("seeds:\n\nseed-to-soil map:\n50 98 2").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "expected at least one seed");
     }
 }
@@ -1,3 +1,5 @@
+use crate::{parse_number, Answer, ParseError, Solution};
+
 fn roots(a: f64, b: f64, c: f64) -> (f64, f64) {
     let d = b * b - 4.0 * a * c;
     let d_sqrt = d.sqrt();
@@ -6,25 +8,76 @@ fn roots(a: f64, b: f64, c: f64) -> (f64, f64) {
     (x1.min(x2), x1.max(x2))
 }
 
-pub fn part1(input: &str) -> usize {
+/// Checks the race can be won, and that working out how far the boat can go
+/// doesn't overflow.
+fn check_race(input: &str, at: &str, t: usize, record: usize) -> Result<(), ParseError> {
+    let hold = t / 2;
+    match hold.checked_mul(t - hold) {
+        None => Err(ParseError::at(input, at, "race is too long to work out")),
+        Some(best) if best <= record => Err(ParseError::at(
+            input,
+            at,
+            format!("record of {record} can't be beaten in {t}ms"),
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Each race's duration and distance record, kept as text since part 2 reads
+/// the digits as a single number.
+pub fn parse(input: &str) -> Result<Vec<(&str, &str)>, ParseError> {
     let mut lines = input.lines();
+    let mut numbers = |label: &str| {
+        let line = lines.next().unwrap_or_default();
+        let Some(numbers) = line.strip_prefix(label) else {
+            return Err(ParseError::at(
+                input,
+                line,
+                format!("expected '{label} ...'"),
+            ));
+        };
+        let numbers = numbers.split_whitespace().collect::<Vec<_>>();
+        for n in numbers.iter() {
+            parse_number::<usize>(input, n)?;
+        }
+        Ok(numbers)
+    };
+    let race_duration = numbers("Time:")?;
+    let distance_record = numbers("Distance:")?;
+    if race_duration.len() != distance_record.len() {
+        let line = input.lines().nth(1).unwrap_or_default();
+        return Err(ParseError::at(
+            input,
+            line,
+            "expected a distance record for every race",
+        ));
+    }
+    let races: Vec<(&str, &str)> = race_duration.into_iter().zip(distance_record).collect();
+    for &(t, record) in races.iter() {
+        check_race(input, t, t.parse().unwrap(), record.parse().unwrap())?;
+    }
+
+    // part 2's single race
+    let line = input.lines().next().unwrap_or_default();
+    let joined = |numbers: String| {
+        numbers
+            .parse::<usize>()
+            .map_err(|_| ParseError::at(input, line, "the races together are too long to work out"))
+    };
+    let t = joined(races.iter().map(|r| r.0).collect())?;
+    let record = joined(races.iter().map(|r| r.1).collect())?;
+    check_race(input, line, t, record)?;
+
+    Ok(races)
+}
+
+pub fn part1(races: &[(&str, &str)]) -> usize {
     const ACCELERATION: usize = 1;
-    let race_duration = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.parse::<usize>().unwrap());
-    let distance_record = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.parse::<usize>().unwrap());
-
-    race_duration
-        .zip(distance_record)
+    races
+        .iter()
         .map(|(t, record)| {
+            let t = t.parse::<usize>().unwrap();
+            let record = record.parse::<usize>().unwrap();
             // let t_hold_max = t / 2;
             let t_hold_record = roots(
                 -(ACCELERATION as f64),
@@ -58,25 +111,18 @@ pub fn part1(input: &str) -> usize {
         .fold(1, |acc, count| acc * count)
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut lines = input.lines();
+pub fn part2(races: &[(&str, &str)]) -> usize {
     const ACCELERATION: usize = 1;
-    let race_duration = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .collect::<Vec<_>>()
-        .join("")
+    let race_duration = races
+        .iter()
+        .map(|r| r.0)
+        .collect::<String>()
         .parse::<usize>()
         .unwrap();
-    let distance_record = lines
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .collect::<Vec<_>>()
-        .join("")
+    let distance_record = races
+        .iter()
+        .map(|r| r.1)
+        .collect::<String>()
         .parse::<usize>()
         .unwrap();
 
@@ -114,8 +160,34 @@ pub fn part2(input: &str) -> usize {
     count
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'i> = Vec<(&'i str, &'i str)>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Vec<(&'i str, &'i str)>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, races: &Vec<(&'_ str, &'_ str)>) -> Option<Answer> {
+        Some(part1(races).into())
+    }
+
+    fn part2(&self, races: &Vec<(&'_ str, &'_ str)>) -> Option<Answer> {
+        Some(part2(races).into())
+    }
 }
 
 #[cfg(test)]
@@ -127,11 +199,26 @@ Distance:  9  40  200"#;
 
     #[test]
     fn day06_sample_part1() {
-        assert_eq!(part1(SAMPLE), 288);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 288);
     }
 
     #[test]
     fn day06_sample_part2() {
-        assert_eq!(part2(SAMPLE), 71503);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 71503);
+    }
+
+    #[test]
+    fn day06_rejects_races_that_cant_be_worked_out() {
+        let error = parse("Time: 7 15\nDistance: 9 56").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(error.message, "record of 56 can't be beaten in 15ms");
+
+        let error = parse("Time: 4294967296 4294967296\nDistance: 9 9").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "the races together are too long to work out");
+
+        let error = parse("Time: 18446744073709551615\nDistance: 9").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.message, "race is too long to work out");
     }
 }
@@ -1,9 +1,77 @@
 mod part1;
 mod part2;
 
-pub use part1::part1;
+pub use part1::{part1, Hand};
 pub use part2::part2;
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+use crate::{parse_number, Answer, ParseError, Solution};
+use part1::Card;
+use std::collections::HashMap;
+
+/// Parses every hand as it's dealt, with `J` as a jack; part 2 plays those as
+/// jokers instead.
+pub fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+    let mut hashmap = HashMap::new();
+    input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let (Some(cards), Some(bid), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(ParseError::at(input, line, "expected five cards and a bid"));
+            };
+            let dealt = cards
+                .char_indices()
+                .map(|(i, c)| {
+                    Card::parse(c)
+                        .ok_or_else(|| ParseError::at(input, &cards[i..], "expected a card"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let Ok(dealt) = dealt.try_into() else {
+                return Err(ParseError::at(input, cards, "expected five cards"));
+            };
+            Ok(Hand::new(dealt, parse_number(input, bid)?, &mut hashmap))
+        })
+        .collect()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'i> = Vec<Hand>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Hand>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, hands: &Vec<Hand>) -> Option<Answer> {
+        Some(part1(hands).into())
+    }
+
+    fn part2(&self, hands: &Vec<Hand>) -> Option<Answer> {
+        Some(part2(hands).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn day07_points_at_bad_cards() {
+        assert!(super::parse("32T3K 765\nT55J5 684\n").is_ok());
+        let error = super::parse("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert!(super::parse("32T3 765\n").is_err());
+        assert!(super::parse("32T3K\n").is_err());
+    }
 }
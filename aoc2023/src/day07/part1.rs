@@ -1,8 +1,7 @@
-use rayon::prelude::*;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Copy, Clone)]
-enum Card {
+pub(super) enum Card {
     Number(u8),
     Jack,
     Queen,
@@ -31,20 +30,20 @@ impl Ord for Card {
 }
 
 impl Card {
-    fn parse(c: char) -> Self {
+    pub(super) fn parse(c: char) -> Option<Self> {
         match c {
-            'T' => Card::Number(10),
-            'J' => Card::Jack,
-            'Q' => Card::Queen,
-            'K' => Card::King,
-            'A' => Card::Ace,
-            n if n >= '2' && n <= '9' => Card::Number(n as u8 - '0' as u8),
-            _ => panic!("invalid card {c}"),
+            'T' => Some(Card::Number(10)),
+            'J' => Some(Card::Jack),
+            'Q' => Some(Card::Queen),
+            'K' => Some(Card::King),
+            'A' => Some(Card::Ace),
+            n if n >= '2' && n <= '9' => Some(Card::Number(n as u8 - '0' as u8)),
+            _ => None,
         }
     }
 }
 
-type Cards = [Card; 5];
+pub(super) type Cards = [Card; 5];
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
 enum HandType {
@@ -57,11 +56,12 @@ enum HandType {
     FiveOfAKind,
 }
 
+/// A hand as dealt, reading `J` as a jack.
 #[derive(PartialEq, Eq, Copy, Clone, Ord)]
-struct Hand {
-    cards: Cards,
+pub struct Hand {
+    pub(super) cards: Cards,
     hand_type: HandType,
-    bid: usize,
+    pub(super) bid: usize,
 }
 
 impl std::fmt::Display for Hand {
@@ -119,14 +119,7 @@ impl PartialOrd for Hand {
 }
 
 impl Hand {
-    fn parse(input: &str, hashmap: &mut HashMap<Card, u8>) -> Self {
-        let mut cards = [Card::Number(0); 5];
-        let mut parts = input.split_whitespace();
-        let mut card_chars = parts.next().unwrap().chars();
-        for i in 0..5 {
-            cards[i] = Card::parse(card_chars.next().expect("card char"));
-        }
-        let bid = parts.next().unwrap().parse().unwrap();
+    pub(super) fn new(cards: Cards, bid: usize, hashmap: &mut HashMap<Card, u8>) -> Self {
         let hand_type = Hand::hand_type(&cards, hashmap);
         Hand {
             cards,
@@ -157,13 +150,8 @@ impl Hand {
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut hands = input
-        .par_lines()
-        .map_with(HashMap::new(), |mut hashmap, line| {
-            Hand::parse(line, &mut hashmap)
-        })
-        .collect::<Vec<_>>();
+pub fn part1(hands: &[Hand]) -> usize {
+    let mut hands = hands.to_vec();
     hands.sort();
     //eprintln!("hands:");
     // for (i, hand) in hands.iter().enumerate() {
@@ -180,6 +168,7 @@ pub fn part1(input: &str) -> usize {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day07::parse;
 
     const SAMPLE: &'static str = r#"32T3K 765
 T55J5 684
@@ -188,6 +177,10 @@ KTJJT 220
 QQQJA 483
 "#;
 
+    fn deal(line: &str) -> Hand {
+        parse(line).unwrap()[0]
+    }
+
     #[test]
     fn day07_part1_correct_ordering() {
         assert!(Card::Number(2) < Card::Number(3));
@@ -213,10 +206,8 @@ QQQJA 483
 
     #[test]
     fn day07_part1_can_parse_hand() {
-        let mut hashmap: HashMap<Card, u8> = HashMap::new();
-
         //eprintln!("Parsing 32T3K 765");
-        let hand = Hand::parse("32T3K 765", &mut hashmap);
+        let hand = deal("32T3K 765");
         assert_eq!(
             hand,
             Hand {
@@ -233,7 +224,7 @@ QQQJA 483
         );
 
         //eprintln!("Parsing QQQJA 483");
-        let hand = Hand::parse("QQQJA 483", &mut hashmap);
+        let hand = deal("QQQJA 483");
         assert_eq!(
             hand,
             Hand {
@@ -246,12 +237,11 @@ QQQJA 483
 
     #[test]
     fn day07_part1_can_order_hands() {
-        let mut hashmap: HashMap<Card, u8> = HashMap::new();
-        let hand1 = Hand::parse("32T3K 765", &mut hashmap);
-        let hand2 = Hand::parse("T55J5 684", &mut hashmap);
-        let hand3 = Hand::parse("KK677 28", &mut hashmap);
-        let hand4 = Hand::parse("KTJJT 220", &mut hashmap);
-        let hand5 = Hand::parse("QQQJA 483", &mut hashmap);
+        let hand1 = deal("32T3K 765");
+        let hand2 = deal("T55J5 684");
+        let hand3 = deal("KK677 28");
+        let hand4 = deal("KTJJT 220");
+        let hand5 = deal("QQQJA 483");
         let mut hands = vec![hand1, hand2, hand3, hand4, hand5];
         hands.sort();
         assert_eq!(hands, vec![hand1, hand4, hand3, hand2, hand5]);
@@ -259,6 +249,6 @@ QQQJA 483
 
     #[test]
     fn day07_sample_part1() {
-        assert_eq!(part1(SAMPLE), 6440);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 6440);
     }
 }
@@ -1,3 +1,4 @@
+use super::part1;
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -30,16 +31,14 @@ impl Ord for Card {
     }
 }
 
-impl Card {
-    fn parse(c: char) -> Self {
-        match c {
-            'J' => Card::Joker,
-            'T' => Card::Number(10),
-            'Q' => Card::Queen,
-            'K' => Card::King,
-            'A' => Card::Ace,
-            n if n >= '2' && n <= '9' => Card::Number(n as u8 - '0' as u8),
-            _ => panic!("invalid card {c}"),
+impl From<part1::Card> for Card {
+    fn from(card: part1::Card) -> Self {
+        match card {
+            part1::Card::Number(n) => Card::Number(n),
+            part1::Card::Jack => Card::Joker,
+            part1::Card::Queen => Card::Queen,
+            part1::Card::King => Card::King,
+            part1::Card::Ace => Card::Ace,
         }
     }
 }
@@ -114,19 +113,14 @@ impl PartialOrd for Hand {
 }
 
 impl Hand {
-    fn parse(input: &str, hashmap: &mut HashMap<Card, u8>) -> Self {
-        let mut cards = [Card::Number(0); 5];
-        let mut parts = input.split_whitespace();
-        let mut card_chars = parts.next().unwrap().chars();
-        for i in 0..5 {
-            cards[i] = Card::parse(card_chars.next().expect("card char"));
-        }
-        let bid = parts.next().unwrap().parse().unwrap();
+    /// The same hand, with its jacks played as jokers.
+    fn with_jokers(hand: &part1::Hand, hashmap: &mut HashMap<Card, u8>) -> Self {
+        let cards = hand.cards.map(Card::from);
         let hand_type = Hand::hand_type(&cards, hashmap);
         Hand {
             cards,
             hand_type,
-            bid,
+            bid: hand.bid,
         }
     }
 
@@ -175,11 +169,11 @@ impl Hand {
     }
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut hands = input
-        .par_lines()
-        .map_with(HashMap::new(), |mut hashmap, line| {
-            Hand::parse(line, &mut hashmap)
+pub fn part2(hands: &[part1::Hand]) -> usize {
+    let mut hands = hands
+        .par_iter()
+        .map_with(HashMap::new(), |hashmap, hand| {
+            Hand::with_jokers(hand, hashmap)
         })
         .collect::<Vec<_>>();
     hands.sort();
@@ -194,6 +188,7 @@ pub fn part2(input: &str) -> usize {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day07::parse;
 
     const SAMPLE: &'static str = r#"32T3K 765
 T55J5 684
@@ -202,6 +197,10 @@ KTJJT 220
 QQQJA 483
 "#;
 
+    fn deal(line: &str) -> Hand {
+        Hand::with_jokers(&parse(line).unwrap()[0], &mut HashMap::new())
+    }
+
     #[test]
     fn day07_part1_correct_ordering() {
         assert!(Card::Joker < Card::Number(2));
@@ -227,9 +226,7 @@ QQQJA 483
 
     #[test]
     fn day07_part2_can_parse_hand() {
-        let mut hashmap: HashMap<Card, u8> = HashMap::new();
-
-        let hand = Hand::parse("32T3K 765", &mut hashmap);
+        let hand = deal("32T3K 765");
         assert_eq!(
             hand,
             Hand {
@@ -245,7 +242,7 @@ QQQJA 483
             }
         );
 
-        let hand = Hand::parse("QQQJA 483", &mut hashmap);
+        let hand = deal("QQQJA 483");
         assert_eq!(
             hand,
             Hand {
@@ -264,12 +261,11 @@ QQQJA 483
 
     #[test]
     fn day07_part2_can_order_hands() {
-        let mut hashmap: HashMap<Card, u8> = HashMap::new();
-        let hand1 = Hand::parse("32T3K 765", &mut hashmap);
-        let hand2 = Hand::parse("T55J5 684", &mut hashmap);
-        let hand3 = Hand::parse("KK677 28", &mut hashmap);
-        let hand4 = Hand::parse("KTJJT 220", &mut hashmap);
-        let hand5 = Hand::parse("QQQJA 483", &mut hashmap);
+        let hand1 = deal("32T3K 765");
+        let hand2 = deal("T55J5 684");
+        let hand3 = deal("KK677 28");
+        let hand4 = deal("KTJJT 220");
+        let hand5 = deal("QQQJA 483");
         let mut hands = vec![hand1, hand2, hand3, hand4, hand5];
         hands.sort();
         assert_eq!(hands, vec![hand1, hand3, hand2, hand5, hand4]);
@@ -277,6 +273,6 @@ QQQJA 483
 
     #[test]
     fn day07_sample_part2() {
-        assert_eq!(part2(SAMPLE), 5905);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 5905);
     }
 }
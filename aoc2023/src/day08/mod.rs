@@ -1,44 +1,81 @@
+use crate::{Answer, ParseError, Solution};
 use rayon::prelude::*;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
 }
 
-fn parse(input: &str) -> (Vec<Direction>, HashMap<&str, (&str, &str)>) {
+pub type Network<'i> = (Vec<Direction>, HashMap<&'i str, (&'i str, &'i str)>);
+
+pub fn parse(input: &str) -> Result<Network<'_>, ParseError> {
     let mut lines = input.lines();
-    let directions = lines.next().expect("directions").chars().map(|c| match c {
-        'L' => Direction::Left,
-        'R' => Direction::Right,
-        _ => panic!("invalid direction"),
-    });
+    let first = lines.next().unwrap_or_default();
+    let directions = first
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(ParseError::at(input, &first[i..], "expected 'L' or 'R'")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if directions.is_empty() {
+        return Err(ParseError::at(
+            input,
+            first,
+            "expected at least one 'L' or 'R'",
+        ));
+    }
 
     let nodes = lines
-        .filter_map(|line| {
-            if line.trim().is_empty() {
-                return None;
-            }
-            let mut parts = line.split(" = ");
-            let name = parts.next().expect("node name");
-            let nodes = parts
-                .next()
-                .expect("nodes")
-                .trim_matches(|c| c == '(' || c == ')');
-            let mut nodes = nodes.splitn(2, ", ");
-            let left = nodes.next().expect("left node");
-            let right = nodes.next().expect("right node");
-
-            Some((name, (left, right)))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let Some((name, nodes)) = line.split_once(" = ") else {
+                return Err(ParseError::at(input, line, "expected 'AAA = (BBB, CCC)'"));
+            };
+            let Some((left, right)) = nodes
+                .strip_prefix('(')
+                .and_then(|nodes| nodes.strip_suffix(')'))
+                .and_then(|nodes| nodes.split_once(", "))
+            else {
+                return Err(ParseError::at(input, nodes, "expected '(BBB, CCC)'"));
+            };
+
+            Ok((name, (left, right)))
         })
-        .collect::<HashMap<&str, (&str, &str)>>();
+        .collect::<Result<HashMap<&str, (&str, &str)>, _>>()?;
+
+    // walking off the map would never finish
+    let mut references: Vec<&str> = nodes.values().flat_map(|&(l, r)| [l, r]).collect();
+    references.sort_by_key(|r| r.as_ptr());
+    if let Some(missing) = references.iter().find(|r| !nodes.contains_key(*r)) {
+        return Err(ParseError::at(
+            input,
+            missing,
+            format!("node '{missing}' isn't defined"),
+        ));
+    }
+    let end = |message| ParseError::at_offset(input, input.len(), message);
+    if !nodes.keys().any(|n| n.ends_with('A')) {
+        return Err(end("no node to start from ('AAA', or ending in 'A')"));
+    }
+    if !nodes.keys().any(|n| n.ends_with('Z')) {
+        return Err(end("no node to finish at ('ZZZ', or ending in 'Z')"));
+    }
+    if nodes.contains_key("AAA") && !nodes.contains_key("ZZZ") {
+        return Err(end("there's an 'AAA' node to start from, but no 'ZZZ'"));
+    }
 
-    (directions.collect(), nodes)
+    Ok((directions, nodes))
 }
 
-pub fn part1(input: &str) -> usize {
-    let (directions, nodes) = parse(input);
+/// Part 2's examples don't have an `AAA` node, so there's nothing to walk.
+pub fn part1((directions, nodes): &Network<'_>) -> Option<usize> {
+    if !nodes.contains_key("AAA") {
+        return None;
+    }
     let mut current = "AAA";
     let mut i = 0;
     loop {
@@ -49,7 +86,7 @@ pub fn part1(input: &str) -> usize {
         };
         i += 1;
         if current == "ZZZ" {
-            return i;
+            return Some(i);
         }
     }
 }
@@ -67,9 +104,8 @@ fn lcm<I: IntoIterator<Item = usize>>(iter: I) -> usize {
     iter.into_iter().fold(1, |a, b| a * b / gcd(a, b))
 }
 
-pub fn part2(input: &str) -> usize {
-    let (directions, nodes) = parse(input);
-    let nodes = nodes
+pub fn part2((directions, nodes): &Network<'_>) -> usize {
+    let starts = nodes
         .keys()
         .par_bridge()
         .filter_map(|n| if n.ends_with("A") { Some(n) } else { None })
@@ -88,11 +124,37 @@ pub fn part2(input: &str) -> usize {
             }
         })
         .collect::<Vec<usize>>();
-    lcm(nodes)
+    lcm(starts)
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input<'i> = Network<'i>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Network<'i>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, network: &Network<'_>) -> Option<Answer> {
+        part1(network).map(Answer::from)
+    }
+
+    fn part2(&self, network: &Network<'_>) -> Option<Answer> {
+        Some(part2(network).into())
+    }
 }
 
 #[cfg(test)]
@@ -126,16 +188,46 @@ XXX = (XXX, XXX)"#;
 
     #[test]
     fn day08_sample1_part1() {
-        assert_eq!(part1(SAMPLE1), 2);
+        assert_eq!(part1(&parse(SAMPLE1).unwrap()), Some(2));
     }
 
     #[test]
     fn day08_sample2_part1() {
-        assert_eq!(part1(SAMPLE2), 6);
+        assert_eq!(part1(&parse(SAMPLE2).unwrap()), Some(6));
+    }
+
+    #[test]
+    fn day08_rejects_networks_that_never_finish() {
+        let error = parse("\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!(error.message, "expected at least one 'L' or 'R'");
+
+        let error = parse("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 8));
+        assert_eq!(error.message, "node 'BBB' isn't defined");
+
+        let error = parse("LR\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!(
+            error.message,
+            "no node to finish at ('ZZZ', or ending in 'Z')"
+        );
+
+        let error = parse("LR\n\nBBB = (ZZZ, ZZZ)\nZZZ = (BBB, BBB)").unwrap_err();
+        assert_eq!(
+            error.message,
+            "no node to start from ('AAA', or ending in 'A')"
+        );
+
+        let error = parse("LR\n\nAAA = (11Z, 11Z)\n11Z = (AAA, AAA)").unwrap_err();
+        assert_eq!(
+            error.message,
+            "there's an 'AAA' node to start from, but no 'ZZZ'"
+        );
+
+        assert_eq!(part1(&parse(SAMPLE3).unwrap()), None);
     }
 
     #[test]
     fn day08_sample3_part2() {
-        assert_eq!(part2(SAMPLE3), 6);
+        assert_eq!(part2(&parse(SAMPLE3).unwrap()), 6);
     }
 }
@@ -1,15 +1,18 @@
+use crate::{parse_number, Answer, ParseError, Solution};
 use rayon::prelude::*;
 
-fn parse(input: &str) -> Vec<Vec<isize>> {
+pub fn parse(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
     input
         .par_lines()
         .map(|line| {
-            line.split_whitespace()
-                .map(|n| {
-                    n.parse::<isize>()
-                        .expect(&format!("failed to parse number: {}", n))
-                })
-                .collect()
+            let history = line
+                .split_whitespace()
+                .map(|n| parse_number(input, n))
+                .collect::<Result<Vec<_>, _>>()?;
+            if history.is_empty() {
+                return Err(ParseError::at(input, line, "expected a history of numbers"));
+            }
+            Ok(history)
         })
         .collect()
 }
@@ -36,44 +39,68 @@ fn derivatives<'i, I: Iterator<Item = &'i isize>>(iter: I) -> Vec<Vec<isize>> {
     derivatives
 }
 
-fn predict_forward(items: Vec<isize>) -> isize {
+fn predict_forward(items: &[isize]) -> isize {
     // naive implementation..
     let derivatives = derivatives(items.iter());
 
+    // a history too short for its differences to reach zero ends with none,
+    // which are as good as zero
     let mut dx = 0;
     for deriv in derivatives.into_iter().rev() {
-        dx += deriv.last().unwrap();
+        dx += deriv.last().unwrap_or(&0);
     }
 
     items.last().unwrap() + dx
 }
 
-fn predict_backward(items: Vec<isize>) -> isize {
+fn predict_backward(items: &[isize]) -> isize {
     // naive implementation..
     let derivatives = derivatives(items.iter());
 
     let mut dx = 0;
     for deriv in derivatives.into_iter().rev() {
-        dx = deriv.first().unwrap() - dx;
+        dx = deriv.first().unwrap_or(&0) - dx;
     }
 
     items.first().unwrap() - dx
 }
 
-pub fn part1(input: &str) -> isize {
-    parse(input).into_par_iter().map(predict_forward).sum()
+pub fn part1(histories: &[Vec<isize>]) -> isize {
+    histories.par_iter().map(|h| predict_forward(h)).sum()
 }
 
-pub fn part2(input: &str) -> isize {
-    parse(input).into_par_iter().map(predict_backward).sum()
+pub fn part2(histories: &[Vec<isize>]) -> isize {
+    histories.par_iter().map(|h| predict_backward(h)).sum()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    let p1 = part1(input);
-    let p1 = usize::try_from(p1).expect("part1: usize overflow");
-    let p2 = part2(input);
-    let p2 = usize::try_from(p2).expect("part2: usize overflow");
-    (Some(p1), Some(p2))
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'i> = Vec<Vec<isize>>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, histories: &Vec<Vec<isize>>) -> Option<Answer> {
+        Some(part1(histories).into())
+    }
+
+    fn part2(&self, histories: &Vec<Vec<isize>>) -> Option<Answer> {
+        Some(part2(histories).into())
+    }
 }
 
 #[cfg(test)]
@@ -86,11 +113,24 @@ mod test {
 
     #[test]
     fn day09_sample_part1() {
-        assert_eq!(part1(SAMPLE), 114);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 114);
     }
 
     #[test]
     fn day09_sample_part2() {
-        assert_eq!(part2(SAMPLE), 2);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn day09_handles_short_histories() {
+        let error = parse("0 3 6\n\n1 3 6").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected a history of numbers")
+        );
+
+        let histories = parse("5\n1 2 4").unwrap();
+        assert_eq!(part1(&histories), 5 + 7);
+        assert_eq!(part2(&histories), 5 + 1);
     }
 }
@@ -1,5 +1,8 @@
+use crate::grid::parse_rows;
+use crate::{Answer, ParseError, Solution};
+
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum Pipe {
+pub enum Pipe {
     Horizontal,
     Vertical,
     NorthEast,
@@ -10,9 +13,11 @@ enum Pipe {
     Ground,
 }
 
-impl From<char> for Pipe {
-    fn from(c: char) -> Self {
-        match c {
+impl TryFrom<char> for Pipe {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        Ok(match c {
             '-' => Self::Horizontal,
             '|' => Self::Vertical,
             'L' => Self::NorthEast,
@@ -21,25 +26,25 @@ impl From<char> for Pipe {
             'F' => Self::SouthEast,
             'S' => Self::Start,
             '.' => Self::Ground,
-            _ => panic!("invalid pipe {}", c),
-        }
+            _ => return Err(c),
+        })
     }
 }
 
-struct LoopStart {
+struct LoopStart<'m> {
     start: (usize, usize),
-    map: Vec<Vec<Pipe>>,
+    map: &'m Vec<Vec<Pipe>>,
 }
 
-impl From<Vec<Vec<Pipe>>> for LoopStart {
-    fn from(map: Vec<Vec<Pipe>>) -> Self {
-        let start = find_start(&map);
+impl<'m> From<&'m Vec<Vec<Pipe>>> for LoopStart<'m> {
+    fn from(map: &'m Vec<Vec<Pipe>>) -> Self {
+        let start = find_start(map);
         Self { start, map }
     }
 }
 
-impl IntoIterator for LoopStart {
-    type IntoIter = Loop;
+impl<'m> IntoIterator for LoopStart<'m> {
+    type IntoIter = Loop<'m>;
     type Item = LoopStep;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -52,11 +57,11 @@ impl IntoIterator for LoopStart {
     }
 }
 
-struct Loop {
+struct Loop<'m> {
     current: (usize, usize),
     previous: (usize, usize),
     distance: usize,
-    map: Vec<Vec<Pipe>>,
+    map: &'m Vec<Vec<Pipe>>,
 }
 
 struct LoopStep {
@@ -64,7 +69,7 @@ struct LoopStep {
     distance: usize,
 }
 
-impl Iterator for Loop {
+impl Iterator for Loop<'_> {
     type Item = LoopStep;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -166,11 +171,16 @@ impl Iterator for Loop {
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<Pipe>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(From::from).collect())
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<Pipe>>, ParseError> {
+    let map = parse_rows(input, |c| Pipe::try_from(c).ok())?;
+    if !map.iter().flatten().any(|&pipe| pipe == Pipe::Start) {
+        return Err(ParseError::at_offset(
+            input,
+            input.len(),
+            "no start 'S' found",
+        ));
+    }
+    Ok(map)
 }
 
 fn find_start(map: &Vec<Vec<Pipe>>) -> (usize, usize) {
@@ -184,8 +194,7 @@ fn find_start(map: &Vec<Vec<Pipe>>) -> (usize, usize) {
     panic!("no start found");
 }
 
-pub fn part1(input: &str) -> usize {
-    let map = parse(input);
+pub fn part1(map: &Vec<Vec<Pipe>>) -> usize {
     let loop_start = LoopStart::from(map);
     loop_start
         .into_iter()
@@ -195,9 +204,8 @@ pub fn part1(input: &str) -> usize {
         / 2
 }
 
-pub fn part2(input: &str) -> usize {
-    let map = parse(input);
-    let start = find_start(&map);
+pub fn part2(map: &Vec<Vec<Pipe>>) -> usize {
+    let start = find_start(map);
     let mut pipe: Vec<Vec<Option<usize>>> = vec![vec![None; map[0].len()]; map.len()];
     pipe[start.1][start.0] = Some(0);
     let loop_start = LoopStart::from(map);
@@ -239,8 +247,34 @@ pub fn part2(input: &str) -> usize {
     inside_count
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'i> = Vec<Vec<Pipe>>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<Pipe>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, map: &Vec<Vec<Pipe>>) -> Option<Answer> {
+        Some(part1(map).into())
+    }
+
+    fn part2(&self, map: &Vec<Vec<Pipe>>) -> Option<Answer> {
+        Some(part2(map).into())
+    }
 }
 
 #[cfg(test)]
@@ -255,7 +289,7 @@ LJ..."#;
 
     #[test]
     fn day10_sample_part1() {
-        assert_eq!(part1(SAMPLE), 8);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 8);
     }
 
     const SAMPLE1: &'static str = r#"...........
@@ -302,21 +336,31 @@ L7JLJL-JLJLJL--JLJ.L"#;
 
     #[test]
     fn day10_sample1_part2() {
-        assert_eq!(part2(SAMPLE1), 4);
+        assert_eq!(part2(&parse(SAMPLE1).unwrap()), 4);
     }
 
     #[test]
     fn day10_sample2_part2() {
-        assert_eq!(part2(SAMPLE2), 4);
+        assert_eq!(part2(&parse(SAMPLE2).unwrap()), 4);
     }
 
     #[test]
     fn day10_sample3_part2() {
-        assert_eq!(part2(SAMPLE3), 8);
+        assert_eq!(part2(&parse(SAMPLE3).unwrap()), 8);
     }
 
     #[test]
     fn day10_sample4_part2() {
-        assert_eq!(part2(SAMPLE4), 10);
+        assert_eq!(part2(&parse(SAMPLE4).unwrap()), 10);
+    }
+
+    #[test]
+    fn day10_points_at_bad_pipes() {
+        let error = parse("S-7\n|X|\nL-J").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "unexpected 'X'");
+
+        let error = parse("F-7\n|.|\nL-J\n").unwrap_err();
+        assert_eq!(error.message, "no start 'S' found");
     }
 }
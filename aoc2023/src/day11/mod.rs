@@ -1,8 +1,12 @@
-fn parse(input: &str) -> Vec<Vec<bool>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(|c| c == '#').collect())
-        .collect()
+use crate::grid::parse_rows;
+use crate::{Answer, ParseError, Solution};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    parse_rows(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
 fn empty_rows(map: &Vec<Vec<bool>>) -> Vec<usize> {
@@ -45,11 +49,10 @@ fn galaxy_pairs(galaxies: &Vec<(usize, usize)>) -> Vec<((usize, usize), (usize,
     pairs
 }
 
-pub fn part1(input: &str) -> usize {
-    let map = parse(input);
-    let empty_rows = empty_rows(&map);
-    let empty_columns = empty_columns(&map);
-    let galaxies = galaxy_locations(&map);
+pub fn part1(map: &Vec<Vec<bool>>) -> usize {
+    let empty_rows = empty_rows(map);
+    let empty_columns = empty_columns(map);
+    let galaxies = galaxy_locations(map);
     let pairs = galaxy_pairs(&galaxies);
 
     pairs
@@ -78,11 +81,10 @@ pub fn part1(input: &str) -> usize {
         .sum()
 }
 
-pub fn part2(input: &str, scale: usize) -> usize {
-    let map = parse(input);
-    let empty_rows = empty_rows(&map);
-    let empty_columns = empty_columns(&map);
-    let galaxies = galaxy_locations(&map);
+pub fn part2(map: &Vec<Vec<bool>>, scale: usize) -> usize {
+    let empty_rows = empty_rows(map);
+    let empty_columns = empty_columns(map);
+    let galaxies = galaxy_locations(map);
     let pairs = galaxy_pairs(&galaxies);
 
     // Reduce scale by 1 since we're already intrinsically counting the row/column in dx/dy
@@ -115,8 +117,34 @@ pub fn part2(input: &str, scale: usize) -> usize {
         .sum::<usize>()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input, 1000000)))
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'i> = Vec<Vec<bool>>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, map: &Vec<Vec<bool>>) -> Option<Answer> {
+        Some(part1(map).into())
+    }
+
+    fn part2(&self, map: &Vec<Vec<bool>>) -> Option<Answer> {
+        Some(part2(map, 1_000_000).into())
+    }
 }
 
 #[cfg(test)]
@@ -136,16 +164,16 @@ mod test {
 
     #[test]
     fn day11_sample_part1() {
-        assert_eq!(part1(SAMPLE), 374);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 374);
     }
 
     #[test]
     fn day11_sample_part2() {
-        assert_eq!(part2(SAMPLE, 10), 1030);
+        assert_eq!(part2(&parse(SAMPLE).unwrap(), 10), 1030);
     }
 
     #[test]
     fn day11_sample_part3() {
-        assert_eq!(part2(SAMPLE, 100), 8410);
+        assert_eq!(part2(&parse(SAMPLE).unwrap(), 100), 8410);
     }
 }
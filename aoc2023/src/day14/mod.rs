@@ -1,30 +1,31 @@
 use std::collections::HashMap;
 
+use crate::grid::parse_rows;
+use crate::{Answer, ParseError, Solution};
 use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Tile {
+pub enum Tile {
     Open,
     Block,
     Rock,
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
         match c {
-            '.' => Tile::Open,
-            '#' => Tile::Block,
-            'O' => Tile::Rock,
-            _ => panic!("Invalid tile: {}", c),
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Block),
+            'O' => Ok(Tile::Rock),
+            _ => Err(c),
         }
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<Tile>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(Tile::from).collect())
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    parse_rows(input, |c| Tile::try_from(c).ok())
 }
 
 fn calculate_load(map: &Vec<Vec<Tile>>) -> usize {
@@ -49,9 +50,8 @@ fn calculate_load(map: &Vec<Vec<Tile>>) -> usize {
         .sum()
 }
 
-pub fn part1(input: &str) -> usize {
-    let map = parse(input);
-    calculate_load(&map)
+pub fn part1(map: &Vec<Vec<Tile>>) -> usize {
+    calculate_load(map)
 }
 
 #[derive(Copy, Clone)]
@@ -143,9 +143,9 @@ fn hash_map(map: &Vec<Vec<Tile>>) -> usize {
 }
 
 // TODO: SO CLOSE!
-pub fn part2(input: &str) -> usize {
-    let map = parse(input);
-    let mut map = RotatableMap::new(map);
+pub fn part2(map: &Vec<Vec<Tile>>) -> usize {
+    // spinning moves the rocks around, so work on a copy
+    let mut map = RotatableMap::new(map.clone());
 
     let mut history: HashMap<usize, usize> = HashMap::new();
 
@@ -170,8 +170,34 @@ pub fn part2(input: &str) -> usize {
     }
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), None)
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'i> = Vec<Vec<Tile>>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Parabolic Reflector Dish"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, map: &Vec<Vec<Tile>>) -> Option<Answer> {
+        Some(part1(map).into())
+    }
+
+    fn part2(&self, _map: &Vec<Vec<Tile>>) -> Option<Answer> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -191,18 +217,18 @@ O.#..O.#.#
 
     #[test]
     fn day14_sample_part1() {
-        assert_eq!(part1(SAMPLE), 136);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 136);
     }
 
     #[test]
     #[ignore]
     fn day14_sample_part2() {
-        assert_eq!(part2(SAMPLE), 64);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 64);
     }
 
     #[test]
     fn can_map_coords() {
-        let map = RotatableMap::new(parse(SAMPLE));
+        let map = RotatableMap::new(parse(SAMPLE).unwrap());
         assert_eq!(map.map_coords((0, 0), Rotation::Zero), (0, 0));
         assert_eq!(map.map_coords((0, 9), Rotation::Zero), (0, 9));
 
@@ -218,7 +244,7 @@ O.#..O.#.#
 
     #[test]
     fn can_tilt() {
-        let mut map = RotatableMap::new(parse(SAMPLE));
+        let mut map = RotatableMap::new(parse(SAMPLE).unwrap());
         map.tilt(Rotation::Zero);
         assert_eq!(
             map.to_string(),
@@ -286,7 +312,7 @@ O.......#O
 
     #[test]
     fn can_spin_cycle() {
-        let mut map = RotatableMap::new(parse(SAMPLE));
+        let mut map = RotatableMap::new(parse(SAMPLE).unwrap());
         spin_cycle(&mut map);
         assert_eq!(
             map.to_string(),
@@ -1,3 +1,4 @@
+use crate::{parse_number, Answer, ParseError, Solution};
 use rayon::prelude::*;
 
 fn hash(input: &[u8]) -> usize {
@@ -10,12 +11,29 @@ fn hash(input: &[u8]) -> usize {
     h
 }
 
-pub fn part1(input: &str) -> usize {
-    input
-        .trim()
-        .par_split(',')
-        .map(|s| hash(s.as_bytes()))
-        .sum()
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    let steps = input.trim().split(',').collect::<Vec<_>>();
+    for step in steps.iter() {
+        let label = get_label(step);
+        match &step[label.len()..] {
+            "-" => {}
+            lens if lens.starts_with('=') => {
+                parse_number::<usize>(input, &lens[1..])?;
+            }
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    step,
+                    "expected a label followed by '-' or '=' and a focal length",
+                ))
+            }
+        }
+    }
+    Ok(steps)
+}
+
+pub fn part1(steps: &[&str]) -> usize {
+    steps.par_iter().map(|s| hash(s.as_bytes())).sum()
 }
 
 fn get_label(input: &str) -> &str {
@@ -27,11 +45,10 @@ fn box_number(input: &str) -> usize {
     hash(input.as_bytes())
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(steps: &[&str]) -> usize {
     let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::default(); 256];
 
-    let steps = input.trim().split(',');
-    for step in steps {
+    for &step in steps {
         let label = get_label(step);
         let box_number = box_number(label);
 
@@ -94,8 +111,34 @@ pub fn part2(input: &str) -> usize {
         .sum()
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'i> = Vec<&'i str>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Lens Library"
+    }
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Vec<&'i str>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, steps: &Vec<&'_ str>) -> Option<Answer> {
+        Some(part1(steps).into())
+    }
+
+    fn part2(&self, steps: &Vec<&'_ str>) -> Option<Answer> {
+        Some(part2(steps).into())
+    }
 }
 
 #[cfg(test)]
@@ -112,11 +155,11 @@ mod test {
 
     #[test]
     fn day15_sample_part1() {
-        assert_eq!(part1(SAMPLE), 1320);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 1320);
     }
 
     #[test]
     fn day15_sample_part2() {
-        assert_eq!(part2(SAMPLE), 145);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 145);
     }
 }
@@ -1,8 +1,10 @@
+use crate::grid::parse_rows;
+use crate::{Answer, ParseError, Solution};
 use rayon::prelude::*;
 use std::collections::HashSet;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
-enum Tile {
+pub enum Tile {
     Empty,
     SplitVertical,
     SplitHorizontal,
@@ -10,15 +12,17 @@ enum Tile {
     BounceBackslash,
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
         match c {
-            '.' => Tile::Empty,
-            '|' => Tile::SplitVertical,
-            '-' => Tile::SplitHorizontal,
-            '/' => Tile::BounceSlash,
-            '\\' => Tile::BounceBackslash,
-            _ => panic!("Invalid tile: {}", c),
+            '.' => Ok(Tile::Empty),
+            '|' => Ok(Tile::SplitVertical),
+            '-' => Ok(Tile::SplitHorizontal),
+            '/' => Ok(Tile::BounceSlash),
+            '\\' => Ok(Tile::BounceBackslash),
+            _ => Err(c),
         }
     }
 }
@@ -137,11 +141,8 @@ fn get_action(map: &Vec<Vec<Tile>>, coord: Coordinate, dir: Direction) -> Action
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<Tile>> {
-    input
-        .lines()
-        .map(|line| line.trim().chars().map(Tile::from).collect())
-        .collect()
+pub fn parse(input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    parse_rows(input.trim_end(), |c| Tile::try_from(c).ok())
 }
 
 fn count_energized_tiles(map: &Vec<Vec<Tile>>, ray: (Coordinate, Direction)) -> usize {
@@ -241,13 +242,11 @@ fn count_energized_tiles(map: &Vec<Vec<Tile>>, ray: (Coordinate, Direction)) ->
     energized_tiles.len()
 }
 
-pub fn part1(input: &str) -> usize {
-    let map = parse(input.trim());
-    count_energized_tiles(&map, ((-1, 0), Direction::Right))
+pub fn part1(map: &Vec<Vec<Tile>>) -> usize {
+    count_energized_tiles(map, ((-1, 0), Direction::Right))
 }
 
-pub fn part2(input: &str) -> usize {
-    let map = parse(input.trim());
+pub fn part2(map: &Vec<Vec<Tile>>) -> usize {
     let (width, height) = (map[0].len() as isize, map.len() as isize);
     let mut rays: Vec<(Coordinate, Direction)> = Vec::new();
     for y in 0..height {
@@ -260,13 +259,39 @@ pub fn part2(input: &str) -> usize {
     }
 
     rays.into_par_iter()
-        .map(|ray| count_energized_tiles(&map, ray))
+        .map(|ray| count_energized_tiles(map, ray))
         .max()
         .unwrap_or(0)
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'i> = Vec<Vec<Tile>>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "The Floor Will Be Lava"
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, map: &Vec<Vec<Tile>>) -> Option<Answer> {
+        Some(part1(map).into())
+    }
+
+    fn part2(&self, map: &Vec<Vec<Tile>>) -> Option<Answer> {
+        Some(part2(map).into())
+    }
 }
 
 #[cfg(test)]
@@ -287,11 +312,11 @@ mod test {
 
     #[test]
     fn day15_sample_part1() {
-        assert_eq!(part1(SAMPLE), 46);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 46);
     }
 
     #[test]
     fn day15_sample_part2() {
-        assert_eq!(part2(SAMPLE), 51);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 51);
     }
 }
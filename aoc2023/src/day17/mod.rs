@@ -1,15 +1,18 @@
 use crate::grid::{Grid, ParseGridChar};
+use crate::{Answer, ParseError, Solution};
 
 struct Parse;
 impl ParseGridChar<u8> for Parse {
-    fn parse_grid_char(c: char) -> u8 {
-        (c as u8) - ('0' as u8)
+    fn parse_grid_char(c: char) -> Option<u8> {
+        c.to_digit(10).map(|d| d as u8)
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = Grid::parse::<Parse>(input);
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse::<Parse>(input)
+}
 
+pub fn part1(grid: &Grid<u8>) -> usize {
     let mut dist: Grid<usize> = Grid::construct(grid.width(), grid.height(), usize::MAX);
     let mut prev: Grid<Option<(usize, usize)>> = Grid::construct(grid.width(), grid.height(), None);
     let mut q: Vec<(usize, usize)> = grid.iter_coords_row_major().collect();
@@ -110,12 +113,38 @@ pub fn part1(input: &str) -> usize {
     s.into_iter().map(|(x, y)| grid.get(x, y) as usize).sum()
 }
 
-pub fn part2(_input: &str) -> usize {
+pub fn part2(_grid: &Grid<u8>) -> usize {
     0
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), None)
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'i> = Grid<u8>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Clumsy Crucible"
+    }
+
+    fn parse(&self, input: &str) -> Result<Grid<u8>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, grid: &Grid<u8>) -> Option<Answer> {
+        Some(part1(grid).into())
+    }
+
+    fn part2(&self, _grid: &Grid<u8>) -> Option<Answer> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +168,6 @@ mod test {
     #[test]
     #[ignore]
     fn day17_sample_part1() {
-        assert_eq!(part1(SAMPLE), 102);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 102);
     }
 }
@@ -1,7 +1,8 @@
+use crate::{Answer, ParseError, Solution};
 use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
@@ -9,7 +10,7 @@ enum Direction {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Instruction {
+pub struct Instruction {
     direction: Direction,
     distance: isize,
 }
@@ -178,35 +179,49 @@ mod parse {
     }
 }
 
-fn parse_input_part1(input: &str) -> Vec<Instruction> {
+fn parse_input_part1(input: &str) -> Result<Vec<Instruction>, ParseError> {
     use parse::parse_instruction_part_1;
 
     input
         .par_lines()
         .map(|line| {
             parse_instruction_part_1(line)
-                .expect("can parse instruction")
-                .1
+                .map(|(_, instruction)| instruction)
+                .map_err(|e| ParseError::from_nom(input, e, "expected 'R 6 (#70c710)'"))
         })
         .collect()
 }
 
-fn parse_input_part2(input: &str) -> Vec<Instruction> {
+fn parse_input_part2(input: &str) -> Result<Vec<Instruction>, ParseError> {
     use parse::parse_instruction_part_2;
 
     input
         .par_lines()
         .map(|line| {
             parse_instruction_part_2(line)
-                .expect("can parse instruction")
-                .1
+                .map(|(_, instruction)| instruction)
+                .map_err(|e| ParseError::from_nom(input, e, "expected 'R 6 (#70c710)'"))
         })
         .collect()
 }
 
-fn generate_polygon(instructions: Vec<Instruction>) -> Vec<(isize, isize)> {
+/// The dig plan, read both the way part 1 reads it and with the distances
+/// and directions from the hex codes that part 2 uses.
+pub struct DigPlan {
+    part1: Vec<Instruction>,
+    part2: Vec<Instruction>,
+}
+
+pub fn parse(input: &str) -> Result<DigPlan, ParseError> {
+    Ok(DigPlan {
+        part1: parse_input_part1(input)?,
+        part2: parse_input_part2(input)?,
+    })
+}
+
+fn generate_polygon(instructions: &[Instruction]) -> Vec<(isize, isize)> {
     instructions
-        .into_iter()
+        .iter()
         .fold(vec![(0isize, 0isize)], |mut polygon, instruction| {
             let (mut x, mut y) = polygon.last().expect("polygon has at least one point");
             match instruction.direction {
@@ -238,20 +253,44 @@ fn shoelace(polygon: &Vec<(isize, isize)>) -> usize {
     (double_area / 2) + 1
 }
 
-pub fn part1(input: &str) -> usize {
-    let instructions = parse_input_part1(input);
-    let polygon = generate_polygon(instructions);
+pub fn part1(plan: &DigPlan) -> usize {
+    let polygon = generate_polygon(&plan.part1);
     shoelace(&polygon)
 }
 
-pub fn part2(input: &str) -> usize {
-    let instructions = parse_input_part2(input);
-    let polygon = generate_polygon(instructions);
+pub fn part2(plan: &DigPlan) -> usize {
+    let polygon = generate_polygon(&plan.part2);
     shoelace(&polygon)
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), Some(part2(input)))
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'i> = DigPlan;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Lavaduct Lagoon"
+    }
+
+    fn parse(&self, input: &str) -> Result<DigPlan, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, plan: &DigPlan) -> Option<Answer> {
+        Some(part1(plan).into())
+    }
+
+    fn part2(&self, plan: &DigPlan) -> Option<Answer> {
+        Some(part2(plan).into())
+    }
 }
 
 #[cfg(test)]
@@ -275,11 +314,11 @@ U 2 (#7a21e3)"#;
 
     #[test]
     fn day17_sample_part1() {
-        assert_eq!(part1(SAMPLE), 62);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 62);
     }
 
     #[test]
     fn day17_sample_part2() {
-        assert_eq!(part2(SAMPLE), 952408144115);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 952408144115);
     }
 }
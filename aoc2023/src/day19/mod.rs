@@ -1,8 +1,9 @@
+use crate::{Answer, ParseError, Solution};
 use nom::{
     branch::alt,
     bytes::complete::take_while1,
     character::complete::{char, digit1, one_of},
-    combinator::opt,
+    combinator::{map_res, opt},
     multi::separated_list1,
     sequence::{delimited, pair, preceded},
     IResult,
@@ -70,22 +71,43 @@ impl std::fmt::Display for Part {
     }
 }
 
-fn parse<'s>(input: &'s str) -> (HashMap<&'s str, Workflow<'s>>, Vec<Part>) {
-    let mut halves = input.split("\n\n");
-    let workflows = halves
-        .next()
-        .unwrap()
+pub struct System<'s> {
+    workflows: HashMap<&'s str, Workflow<'s>>,
+    parts: Vec<Part>,
+}
+
+pub fn parse(input: &str) -> Result<System<'_>, ParseError> {
+    let Some((workflows, parts)) = input.split_once("\n\n") else {
+        return Err(ParseError::at_offset(
+            input,
+            input.len(),
+            "expected workflows and parts separated by a blank line",
+        ));
+    };
+    let workflows = workflows
         .lines()
-        .map(parser::parse_workflow)
-        .map(|w| (w.name, w))
-        .collect();
-    let parts = halves
-        .next()
-        .unwrap()
+        .map(|line| {
+            parser::parse_workflow(line)
+                .map(|w| (w.name, w))
+                .map_err(|e| {
+                    ParseError::from_nom(input, e, "expected a workflow like 'px{a<2006:qkq,rfg}'")
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    let parts = parts
         .lines()
-        .map(parser::parse_part)
-        .collect();
-    (workflows, parts)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            parser::parse_part(line).map_err(|e| {
+                ParseError::from_nom(
+                    input,
+                    e,
+                    "expected a part like '{x=787,m=2655,a=1222,s=2876}'",
+                )
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(System { workflows, parts })
 }
 
 mod parser {
@@ -124,7 +146,7 @@ mod parser {
     }
 
     fn value(input: &str) -> IResult<&str, usize> {
-        digit1(input).map(|(input, digits)| (input, digits.parse().unwrap()))
+        map_res(digit1, |digits: &str| digits.parse())(input)
     }
 
     fn rule_check(input: &str) -> IResult<&str, RuleCheck> {
@@ -167,12 +189,9 @@ mod parser {
         separated_list1(char(','), rule)(input)
     }
 
-    pub fn parse_workflow(line: &str) -> Workflow {
-        let workflow = pair(workflow_name, delimited(char('{'), rules, char('}')))(line);
-        match workflow {
-            Ok((_, (name, rules))) => Workflow { name, rules },
-            Err(_) => panic!("Failed to parse workflow: '{line}'"),
-        }
+    pub fn parse_workflow(line: &str) -> Result<Workflow<'_>, nom::Err<nom::error::Error<&str>>> {
+        let (_, (name, rules)) = pair(workflow_name, delimited(char('{'), rules, char('}')))(line)?;
+        Ok(Workflow { name, rules })
     }
 
     fn part_categories(input: &str) -> IResult<&str, Part> {
@@ -187,12 +206,9 @@ mod parser {
         Ok((input, Part { x, m, a, s }))
     }
 
-    pub fn parse_part(line: &str) -> Part {
-        let part = delimited(char('{'), part_categories, char('}'))(line);
-        match part {
-            Ok((_, part)) => part,
-            Err(_) => panic!("Failed to parse part: '{line}'"),
-        }
+    pub fn parse_part(line: &str) -> Result<Part, nom::Err<nom::error::Error<&str>>> {
+        let (_, part) = delimited(char('{'), part_categories, char('}'))(line)?;
+        Ok(part)
     }
 
     #[cfg(test)]
@@ -338,7 +354,7 @@ mod parser {
         #[test]
         fn can_parse_workflow() {
             let workflow = "px{a<2006:qkq,m>2090:A,rfg}";
-            let parsed = parse_workflow(workflow);
+            let parsed = parse_workflow(workflow).unwrap();
             assert_eq!(parsed.name, "px");
             assert_eq!(parsed.rules.len(), 3);
             assert_eq!(
@@ -365,7 +381,7 @@ mod parser {
         #[test]
         fn can_parse_part() {
             let part = "{x=787,m=2655,a=1222,s=2876}";
-            let parsed = parse_part(part);
+            let parsed = parse_part(part).unwrap();
             assert_eq!(parsed.x, 787);
             assert_eq!(parsed.m, 2655);
             assert_eq!(parsed.a, 1222);
@@ -408,21 +424,47 @@ fn is_part_accepted<'s>(workflows: &'s HashMap<&'s str, Workflow<'s>>, part: Par
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let (workflows, parts) = parse(input);
-    parts
-        .into_par_iter()
-        .filter(|part| is_part_accepted(&workflows, *part))
+pub fn part1(system: &System<'_>) -> usize {
+    system
+        .parts
+        .par_iter()
+        .filter(|part| is_part_accepted(&system.workflows, **part))
         .map(|part| part.rating())
         .sum()
 }
 
-pub fn part2(_input: &str) -> usize {
+pub fn part2(_system: &System<'_>) -> usize {
     0
 }
 
-pub fn run(input: &str) -> (Option<usize>, Option<usize>) {
-    (Some(part1(input)), None)
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'i> = System<'i>;
+
+    fn year(&self) -> u32 {
+        2023
+    }
+
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Aplenty"
+    }
+
+    fn parse<'i>(&self, input: &'i str) -> Result<System<'i>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, system: &System<'_>) -> Option<Answer> {
+        Some(part1(system).into())
+    }
+
+    fn part2(&self, _system: &System<'_>) -> Option<Answer> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -450,12 +492,12 @@ hdj{m>838:A,pv}
 
     #[test]
     fn day19_part1_sample() {
-        assert_eq!(part1(SAMPLE), 19114);
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), 19114);
     }
 
     #[test]
     #[ignore]
     fn day19_part2_sample() {
-        assert_eq!(part2(SAMPLE), 167409079868000);
+        assert_eq!(part2(&parse(SAMPLE).unwrap()), 167409079868000);
     }
 }
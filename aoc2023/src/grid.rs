@@ -1,9 +1,44 @@
+use crate::ParseError;
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::fmt::{Display, Formatter};
 
 pub trait ParseGridChar<T: Copy + Debug> {
-    fn parse_grid_char(c: char) -> T;
+    /// The cell for `c`, or `None` if it doesn't belong in the grid.
+    fn parse_grid_char(c: char) -> Option<T>;
+}
+
+/// Parses a rectangular grid of characters, pointing at any character `parse`
+/// doesn't accept and at rows that are a different width from the first.
+pub fn parse_rows<T>(
+    input: &str,
+    parse: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    // blank lines after the grid aren't short rows
+    for line in input.trim_end_matches(['\r', '\n']).lines() {
+        let row = line
+            .char_indices()
+            .map(|(i, c)| {
+                parse(c)
+                    .ok_or_else(|| ParseError::at(input, &line[i..], format!("unexpected '{c}'")))
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected {} columns, found {}", first.len(), row.len()),
+                ));
+            }
+        }
+        rows.push(row);
+    }
+    if rows.first().is_none_or(|row| row.is_empty()) {
+        return Err(ParseError::at(input, input, "expected a grid"));
+    }
+    Ok(rows)
 }
 
 pub struct Grid<T: Copy + Debug> {
@@ -42,12 +77,8 @@ impl<T: Copy + Debug> Grid<T> {
         self.data.len()
     }
 
-    pub fn parse<P: ParseGridChar<T>>(input: &str) -> Self {
-        let data = input
-            .lines()
-            .map(|line| line.chars().map(P::parse_grid_char).collect())
-            .collect();
-        Self::new(data)
+    pub fn parse<P: ParseGridChar<T>>(input: &str) -> Result<Self, ParseError> {
+        parse_rows(input, P::parse_grid_char).map(Self::new)
     }
 
     pub fn neighbour_coords_cardinal(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
@@ -118,3 +149,25 @@ impl<T: Copy + Debug + PartialEq> PartialEq for Grid<T> {
 }
 
 impl<T: Copy + Debug + PartialEq> Eq for Grid<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cell(c: char) -> Option<char> {
+        matches!(c, '.' | '#').then_some(c)
+    }
+
+    #[test]
+    fn ignores_trailing_blank_lines() {
+        let rows = parse_rows("#.\n.#\n\n\r\n", cell).unwrap();
+        assert_eq!(rows, vec![vec!['#', '.'], vec!['.', '#']]);
+
+        let error = parse_rows("#.\n\n.#\n", cell).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected 2 columns, found 0");
+
+        let error = parse_rows("\n\n", cell).unwrap_err();
+        assert_eq!(error.message, "expected a grid");
+    }
+}
@@ -1,77 +1,255 @@
-use anyhow::{Context, Result};
+use crate::cli::Headless;
+use crate::puzzle;
+use anyhow::{bail, Context, Result};
 use chrono::TimeZone;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 
-pub fn load_inputs() -> Result<()> {
-    let inputs = check_missing_inputs();
+/// Where to reach Advent of Code. Set `AOC_BASE_URL` to point this at a local
+/// stand-in server instead.
+pub fn base_url() -> String {
+    std::env::var("AOC_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| "https://adventofcode.com".to_string())
+}
+
+/// Inputs are kept per year, in `inputs/YYYY/dayNN.txt`.
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    Path::new("inputs")
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Details about where an input came from, kept in `inputs/YYYY/dayNN.meta.toml`
+/// so that corrupted inputs can be spotted later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMetadata {
+    pub downloaded_at: chrono::DateTime<chrono::Utc>,
+    pub bytes: usize,
+    pub sha256: String,
+}
+
+impl InputMetadata {
+    fn for_input(input: &str) -> Self {
+        InputMetadata {
+            downloaded_at: chrono::Utc::now(),
+            bytes: input.len(),
+            sha256: sha256(input),
+        }
+    }
+
+    fn to_toml(&self) -> String {
+        format!(
+            "downloaded_at = \"{}\"\nbytes = {}\nsha256 = \"{}\"\n",
+            self.downloaded_at.to_rfc3339(),
+            self.bytes,
+            self.sha256
+        )
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let table = contents.parse::<toml::Table>()?;
+        let field = |name: &str| {
+            table
+                .get(name)
+                .with_context(|| format!("Missing '{name}' in input metadata"))
+        };
+        let downloaded_at = field("downloaded_at")?
+            .as_str()
+            .context("'downloaded_at' must be a string")?;
+        let downloaded_at = chrono::DateTime::parse_from_rfc3339(downloaded_at)
+            .context("Invalid 'downloaded_at'")?
+            .with_timezone(&chrono::Utc);
+        let bytes = field("bytes")?
+            .as_integer()
+            .context("'bytes' must be an integer")?;
+        let sha256 = field("sha256")?
+            .as_str()
+            .context("'sha256' must be a string")?;
+        Ok(InputMetadata {
+            downloaded_at,
+            bytes: bytes as usize,
+            sha256: sha256.to_string(),
+        })
+    }
+}
+
+pub fn metadata_path(year: u32, day: u32) -> PathBuf {
+    input_path(year, day).with_extension("meta.toml")
+}
+
+fn sha256(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input.as_bytes()))
+}
+
+/// Text AoC serves in place of an input when something has gone wrong, such
+/// as an expired session cookie or asking before the puzzle unlocks.
+const ERROR_MARKERS: &[&str] = &[
+    "Puzzle inputs differ by user",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+];
+
+/// The first line of a response, for error messages.
+fn summary(body: &str) -> &str {
+    let line = body.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let end = line
+        .char_indices()
+        .nth(100)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len());
+    line[..end].trim()
+}
+
+/// Checks that a downloaded body looks like a puzzle input rather than an
+/// error page.
+pub fn validate_input(body: &str) -> Result<()> {
+    if body.trim().is_empty() {
+        bail!("The input is empty");
+    }
+    if ERROR_MARKERS.iter().any(|marker| body.contains(marker)) {
+        bail!("Got an error instead of an input: \"{}\"", summary(body));
+    }
+    let start = body.trim_start().to_ascii_lowercase();
+    if start.starts_with("<!doctype") || start.starts_with("<html") {
+        bail!("Got an HTML page instead of an input");
+    }
+    Ok(())
+}
+
+/// Describes what's wrong with an existing input, if anything: either it's an
+/// error page that was saved by mistake, or it no longer matches its metadata.
+pub fn input_problem(input: &Path, metadata: &Path) -> Option<String> {
+    let contents = match std::fs::read_to_string(input) {
+        Ok(contents) => contents,
+        Err(e) => return Some(format!("can't be read: {e}")),
+    };
+    if let Err(e) = validate_input(&contents) {
+        return Some(e.to_string());
+    }
+    // inputs downloaded before metadata was recorded don't have any
+    let metadata = match std::fs::read_to_string(metadata) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => return Some(format!("metadata can't be read: {e}")),
+    };
+    let metadata = match InputMetadata::parse(&metadata) {
+        Ok(metadata) => metadata,
+        Err(e) => return Some(format!("metadata is invalid: {e}")),
+    };
+    if metadata.bytes != contents.len() || metadata.sha256 != sha256(&contents) {
+        return Some("doesn't match the size or SHA-256 it was downloaded with".to_string());
+    }
+    None
+}
+
+/// Finds inputs for the given days that exist but are corrupted, along with
+/// what's wrong with them.
+pub fn check_corrupted_inputs(year: u32, days: &[u32]) -> Vec<(u32, String)> {
+    days.iter()
+        .copied()
+        .filter(|&day| input_path(year, day).exists())
+        .filter_map(|day| {
+            input_problem(&input_path(year, day), &metadata_path(year, day))
+                .map(|problem| (day, problem))
+        })
+        .collect()
+}
+
+/// Makes sure the inputs for the given days are available, downloading any
+/// missing ones if allowed to. Days that still have no input are skipped by
+/// the runner.
+pub fn load_inputs(year: u32, days: &[u32], headless: Headless) -> Result<()> {
+    let inputs = check_missing_inputs(year, days);
     if inputs.is_empty() {
         return Ok(());
     }
 
-    println!("The following inputs are missing:");
+    eprintln!("The following inputs are missing:");
     for day in inputs.iter() {
-        println!("    Day {}", day);
+        eprintln!("    {year} day {}", day);
     }
-    println!();
+    eprintln!();
 
-    let confirm = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Do you want to download these inputs now?")
-        .interact()
-        .context("Failed to get confirmation")?;
-    if !confirm {
+    if !headless.can_download() {
+        eprintln!("Not downloading them, skipping those days.");
         return Ok(());
     }
+    if !headless.yes {
+        if !headless.can_prompt() {
+            eprintln!("Not downloading them without --yes, skipping those days.");
+            return Ok(());
+        }
+        let confirm = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do you want to download these inputs now?")
+            .interact()
+            .context("Failed to get confirmation")?;
+        if !confirm {
+            return Ok(());
+        }
+    }
 
-    let session = get_session().with_context(|| "Failed to get AOC session auth")?;
-    inputs.into_iter().try_for_each(|day| {
-        download_input(day, &session)?;
-        println!("Downloaded day {day}!");
+    download_inputs(year, &inputs, headless)
+}
+
+pub fn download_inputs(year: u32, days: &[u32], headless: Headless) -> Result<()> {
+    if headless.offline {
+        bail!("Can't download inputs while offline");
+    }
+    let session = get_session(headless).with_context(|| "Failed to get AOC session auth")?;
+    days.iter().try_for_each(|&day| {
+        download_input(year, day, &session)?;
+        eprintln!("Downloaded {year} day {day}!");
         Ok::<(), anyhow::Error>(())
     })?;
 
     Ok(())
 }
 
-fn check_missing_inputs() -> Vec<u32> {
+/// The moment the given day's puzzle unlocks.
+pub fn unlock_time(year: u32, day: u32) -> chrono::DateTime<chrono::FixedOffset> {
     // timezone where AOC is hosted
     let tz = chrono::FixedOffset::west_opt(5 * 3600).expect("Valid timezone");
+    tz.with_ymd_and_hms(year as i32, 12, day, 0, 0, 1)
+        .earliest()
+        .expect("Valid date")
+}
 
+pub fn check_missing_inputs(year: u32, days: &[u32]) -> Vec<u32> {
     let now = chrono::Utc::now();
-    (1..=25)
-        .filter(|day| {
-            let date = tz
-                .with_ymd_and_hms(2023, 12, *day, 0, 0, 1)
-                .earliest()
-                .expect("Valid date");
-            now >= date
-        })
-        .filter(|day| {
-            !std::path::Path::new("inputs")
-                .join(&format!("day{:02}.txt", day))
-                .exists()
-        })
+    days.iter()
+        .copied()
+        .filter(|&day| now >= unlock_time(year, day))
+        .filter(|&day| !input_path(year, day).exists())
         .collect()
 }
 
-fn get_session() -> Result<String> {
+/// Gets the session cookie from `AOC_SESSION`, asking for it if it isn't set
+/// and we're able to prompt.
+pub fn get_session(headless: Headless) -> Result<String> {
     match std::env::var("AOC_SESSION") {
         Ok(session) => Ok(session),
         Err(_) => {
-            println!("Inputs are missing and env var AOC_SESSION is not set!");
-            println!("Please set AOC_SESSION to your AOC session cookie.");
-            println!("You can find this by logging into AOC and looking at the value of the 'session' cookie.");
-            println!(
+            eprintln!("Env var AOC_SESSION is not set!");
+            eprintln!("Please set AOC_SESSION to your AOC session cookie.");
+            eprintln!("You can find this by logging into AOC and looking at the value of the 'session' cookie.");
+            eprintln!(
                 "It should look something like this: 53616c7465645f5f6a0a0a0a0a0a0a0a0a0a0a0a"
             );
-            println!("You can set it by running:");
-            println!("    export AOC_SESSION=53616c7465645f5f6a0a0a0a0a0a0a0a0a0a0a0a");
-            println!("Or by adding it to a .env file in the root of this repo, like this:");
-            println!("    echo 'AOC_SESSION=53616c7465645f5f6a0a0a0a0a0a0a0a0a0a0a0a' >> .env");
-            println!("Be sure not to check this file into git! Add it to your .gitignore:");
-            println!("    echo '.env' >> .gitignore");
-            println!("Then run this command again.");
-
-            println!("For now, you can just enter your session cookie here:");
+            eprintln!("You can set it by running:");
+            eprintln!("    export AOC_SESSION=53616c7465645f5f6a0a0a0a0a0a0a0a0a0a0a0a");
+            eprintln!("Or by adding it to a .env file in the root of this repo, like this:");
+            eprintln!("    echo 'AOC_SESSION=53616c7465645f5f6a0a0a0a0a0a0a0a0a0a0a0a' >> .env");
+            eprintln!("Be sure not to check this file into git! Add it to your .gitignore:");
+            eprintln!("    echo '.env' >> .gitignore");
+            eprintln!("Then run this command again.");
+
+            if !headless.can_prompt() {
+                bail!("AOC_SESSION is not set");
+            }
+
+            eprintln!("For now, you can just enter your session cookie here:");
             let session = Input::<String>::with_theme(&ColorfulTheme::default())
                 .with_prompt("AOC session cookie")
                 .interact()
@@ -81,19 +259,159 @@ fn get_session() -> Result<String> {
     }
 }
 
-fn download_input(day: u32, session: &str) -> Result<()> {
-    let url = format!("https://adventofcode.com/2023/day/{day}/input");
-    let body = ureq::get(&url)
+/// Fetches a page from AoC with the session cookie, turning error responses
+/// into errors that mention `what` was being fetched.
+pub fn fetch_page(url: &str, session: &str, what: &str) -> Result<String> {
+    let response = match ureq::get(url)
         .set("Cookie", &format!("session={}", session))
         .call()
-        .with_context(|| format!("Failed to communicate with AOC server for day {day}"))?
+    {
+        Ok(response) => response,
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            bail!(
+                "AOC server responded with {status} for {what}: \"{}\"",
+                summary(&body)
+            );
+        }
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to communicate with AOC server for {what}"))
+        }
+    };
+    if response.status() != 200 {
+        bail!("AOC server responded with {} for {what}", response.status());
+    }
+    response
         .into_string()
-        .with_context(|| format!("Failed to download input for day {}", day))?;
-    let path = std::path::Path::new("inputs").join(&format!("day{:02}.txt", day));
-    std::fs::write(&path, body).with_context(|| {
+        .with_context(|| format!("Failed to download {what}"))
+}
+
+/// Downloads an input, refusing anything that isn't a successful response
+/// containing something that looks like an input.
+pub fn fetch_input(base_url: &str, session: &str, year: u32, day: u32) -> Result<String> {
+    let url = format!("{base_url}/{year}/day/{day}/input");
+    let body = fetch_page(&url, session, &format!("{year} day {day}"))?;
+    validate_input(&body)
+        .with_context(|| format!("Refusing to save input for {year} day {day}"))?;
+    Ok(body)
+}
+
+pub fn download_input(year: u32, day: u32, session: &str) -> Result<()> {
+    let body = fetch_input(&base_url(), session, year, day)?;
+    let path = input_path(year, day);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create inputs directory: {}", dir.display()))?;
+    }
+    std::fs::write(&path, &body).with_context(|| {
         format!(
-            "Failed to write input for day {day} to file: {}",
+            "Failed to write input for {year} day {day} to file: {}",
             path.display()
         )
-    })
+    })?;
+    let metadata_path = metadata_path(year, day);
+    std::fs::write(&metadata_path, InputMetadata::for_input(&body).to_toml()).with_context(
+        || {
+            format!(
+                "Failed to write input metadata for {year} day {day} to file: {}",
+                metadata_path.display()
+            )
+        },
+    )?;
+    // the description is nice to have offline, but not worth failing over
+    if let Err(e) = puzzle::download_puzzle(year, day, session) {
+        eprintln!("Failed to download the puzzle for {year} day {day}: {e:#}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stand_in;
+
+    #[test]
+    fn rejects_error_pages() {
+        assert!(validate_input("1abc2\npqr3stu8vwx\n").is_ok());
+        assert!(validate_input("").is_err());
+        assert!(validate_input(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_err());
+        assert!(validate_input(
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+        )
+        .is_err());
+        assert!(validate_input("<!DOCTYPE html>\n<html lang=\"en-us\"></html>").is_err());
+    }
+
+    #[test]
+    fn can_fetch_input() {
+        let (base_url, _) = stand_in::serve_once("200 OK", "text/plain", "1abc2\ntreb7uchet\n");
+        let body = fetch_input(&base_url, "abc123", 2023, 1).unwrap();
+        assert_eq!(body, "1abc2\ntreb7uchet\n");
+    }
+
+    #[test]
+    fn refuses_failed_downloads() {
+        let (base_url, _) = stand_in::serve_once(
+            "400 Bad Request",
+            "text/plain",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        let err = fetch_input(&base_url, "expired", 2023, 1).unwrap_err();
+        assert!(format!("{err:#}").contains("400"), "{err:#}");
+
+        let (base_url, _) = stand_in::serve_once("404 Not Found", "text/plain", "404 Not Found\n");
+        assert!(fetch_input(&base_url, "abc123", 2023, 25).is_err());
+
+        let (base_url, _) = stand_in::serve_once(
+            "200 OK",
+            "text/plain",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        assert!(fetch_input(&base_url, "expired", 2023, 1).is_err());
+    }
+
+    #[test]
+    fn metadata_round_trips() {
+        let metadata = InputMetadata::for_input("1abc2\n");
+        assert_eq!(metadata.bytes, 6);
+        assert_eq!(
+            metadata.sha256,
+            "cd0dc6e713bd439c992dcc800a29d09410b9c3dbf2655cc11eef3cec34b39bc5"
+        );
+        let parsed = InputMetadata::parse(&metadata.to_toml()).unwrap();
+        assert_eq!(parsed.bytes, metadata.bytes);
+        assert_eq!(parsed.sha256, metadata.sha256);
+        assert_eq!(
+            parsed.downloaded_at.timestamp(),
+            metadata.downloaded_at.timestamp()
+        );
+    }
+
+    #[test]
+    fn can_find_corrupted_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("day01.txt");
+        let metadata = dir.path().join("day01.meta.toml");
+
+        std::fs::write(&input, "1abc2\n").unwrap();
+        assert_eq!(input_problem(&input, &metadata), None);
+
+        std::fs::write(&metadata, InputMetadata::for_input("1abc2\n").to_toml()).unwrap();
+        assert_eq!(input_problem(&input, &metadata), None);
+
+        std::fs::write(&input, "1abc3\n").unwrap();
+        assert!(input_problem(&input, &metadata).is_some());
+
+        std::fs::remove_file(&metadata).unwrap();
+        std::fs::write(
+            &input,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )
+        .unwrap();
+        assert!(input_problem(&input, &metadata).is_some());
+    }
 }
@@ -0,0 +1,307 @@
+use crate::cli::Headless;
+use crate::inputs;
+use anyhow::{bail, Context, Result};
+use chrono::TimeZone;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// AoC asks that private leaderboards aren't fetched more often than this.
+pub const CACHE_FOR: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u64,
+    pub last_star: Option<i64>,
+    /// When each part's star was earned, by day.
+    pub days: BTreeMap<u32, [Option<i64>; 2]>,
+}
+
+/// Reads the leaderboard's JSON, with the members ordered by local score
+/// like they are on the site.
+pub fn parse(json: &str) -> Result<Vec<Member>> {
+    let value: serde_json::Value = serde_json::from_str(json).context("Invalid leaderboard")?;
+    let Some(members) = value.get("members").and_then(|m| m.as_object()) else {
+        bail!("Leaderboard doesn't have any members");
+    };
+    let mut parsed = Vec::new();
+    for member in members.values() {
+        let number = |name: &str| member.get(name).and_then(|v| v.as_u64());
+        let Some(id) = number("id") else {
+            bail!("Leaderboard member is missing their id");
+        };
+        let name = match member.get("name").and_then(|n| n.as_str()) {
+            Some(name) => name.to_string(),
+            None => format!("(anonymous user #{id})"),
+        };
+        let mut days = BTreeMap::new();
+        if let Some(levels) = member
+            .get("completion_day_level")
+            .and_then(|l| l.as_object())
+        {
+            for (day, parts) in levels.iter() {
+                let Ok(day) = day.parse::<u32>() else {
+                    bail!("Invalid day '{day}' in leaderboard");
+                };
+                let star = |part: &str| parts.get(part)?.get("get_star_ts")?.as_i64();
+                days.insert(day, [star("1"), star("2")]);
+            }
+        }
+        parsed.push(Member {
+            id,
+            name,
+            local_score: number("local_score").unwrap_or(0),
+            stars: number("stars").unwrap_or(0),
+            last_star: member
+                .get("last_star_ts")
+                .and_then(|t| t.as_i64())
+                .filter(|&t| t > 0),
+            days,
+        });
+    }
+    parsed.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+    Ok(parsed)
+}
+
+/// Cached leaderboards are kept with the inputs, in
+/// `inputs/YYYY/leaderboard-ID.json`.
+pub fn cache_path(year: u32, id: &str) -> PathBuf {
+    Path::new("inputs")
+        .join(year.to_string())
+        .join(format!("leaderboard-{id}.json"))
+}
+
+/// How long ago the cached copy was fetched, if there is one.
+pub fn cache_age(path: &Path) -> Option<Duration> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+pub fn fetch_leaderboard(base_url: &str, session: &str, year: u32, id: &str) -> Result<String> {
+    let url = format!("{base_url}/{year}/leaderboard/private/view/{id}.json");
+    let body = inputs::fetch_page(&url, session, &format!("{year} leaderboard {id}"))?;
+    // a bad session gets redirected to the login page rather than an error
+    parse(&body).with_context(|| format!("Unexpected response for {year} leaderboard {id}"))?;
+    Ok(body)
+}
+
+/// Loads the leaderboard, only fetching it again if the cached copy is older
+/// than [`CACHE_FOR`].
+pub fn load(year: u32, id: &str, headless: Headless) -> Result<(Vec<Member>, Duration)> {
+    let path = cache_path(year, id);
+    let age = cache_age(&path);
+    let fresh = age.is_some_and(|age| age < CACHE_FOR);
+    if let (Some(age), true) = (age, fresh || !headless.can_download()) {
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read leaderboard from {}", path.display()))?;
+        return Ok((parse(&json)?, age));
+    }
+    if !headless.can_download() {
+        bail!("The {year} leaderboard {id} hasn't been downloaded");
+    }
+
+    let session =
+        inputs::get_session(headless).with_context(|| "Failed to get AOC session auth")?;
+    let json = fetch_leaderboard(&inputs::base_url(), &session, year, id)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create inputs directory: {}", dir.display()))?;
+    }
+    std::fs::write(&path, &json)
+        .with_context(|| format!("Failed to write leaderboard to {}", path.display()))?;
+    Ok((parse(&json)?, Duration::ZERO))
+}
+
+/// Star timestamps are shown in the timezone puzzles unlock in.
+fn format_time(timestamp: i64, format: &str) -> String {
+    let tz = chrono::FixedOffset::west_opt(5 * 3600).expect("Valid timezone");
+    match tz.timestamp_opt(timestamp, 0).single() {
+        Some(time) => time.format(format).to_string(),
+        None => "?".to_string(),
+    }
+}
+
+/// How long after the puzzle unlocked a star was earned.
+fn format_since_unlock(year: u32, day: u32, timestamp: i64) -> String {
+    let unlock = inputs::unlock_time(year, day).timestamp() - 1;
+    let elapsed = (timestamp - unlock).max(0);
+    let (hours, minutes, seconds) = (elapsed / 3600, elapsed / 60 % 60, elapsed % 60);
+    if hours >= 24 {
+        format!(">24h ({})", format_time(timestamp, "%b %d %H:%M"))
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Writes the leaderboard with a column of stars per day, gold for both
+/// parts and silver for just the first.
+pub fn write_table<W: WriteColor>(out: &mut W, members: &[Member]) -> Result<()> {
+    let last_day = members
+        .iter()
+        .flat_map(|m| m.days.keys())
+        .max()
+        .copied()
+        .unwrap_or(0);
+    let name_width = members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+
+    let days: String = (1..=last_day).map(|d| (d % 10).to_string()).collect();
+    writeln!(
+        out,
+        "{:>4} {:>5}  {days}  {:<name_width$}  {:>5}  Last star",
+        "", "Score", "Name", "Stars"
+    )?;
+    for (rank, member) in members.iter().enumerate() {
+        write!(out, "{:>3}) {:>5}  ", rank + 1, member.local_score)?;
+        for day in 1..=last_day {
+            match member.days.get(&day) {
+                Some([_, Some(_)]) => {
+                    out.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
+                    write!(out, "*")?;
+                }
+                Some([Some(_), None]) => {
+                    out.set_color(ColorSpec::new().set_fg(Some(Color::White)))?;
+                    write!(out, "*")?;
+                }
+                _ => {
+                    out.set_color(
+                        ColorSpec::new()
+                            .set_fg(Some(Color::Black))
+                            .set_intense(true),
+                    )?;
+                    write!(out, ".")?;
+                }
+            }
+            out.reset()?;
+        }
+        let last_star = member
+            .last_star
+            .map(|t| format_time(t, "%b %d %H:%M:%S"))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "  {:<name_width$}  {:>5}  {last_star}",
+            member.name, member.stars
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes when each member earned their stars for a single day, measured from
+/// when it unlocked.
+pub fn write_day<W: Write>(out: &mut W, members: &[Member], year: u32, day: u32) -> Result<()> {
+    let name_width = members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+    writeln!(
+        out,
+        "{:<name_width$}  {:>8}  {:>8}",
+        "Name", "Part 1", "Part 2"
+    )?;
+    let mut members: Vec<_> = members
+        .iter()
+        .filter_map(|m| Some((m, m.days.get(&day)?)))
+        .collect();
+    // fastest first, like the global leaderboard
+    members.sort_by_key(|(_, [part1, part2])| (part2.is_none(), *part2, *part1));
+    for (member, stars) in members.iter() {
+        let [part1, part2] = stars.map(|star| {
+            star.map(|t| format_since_unlock(year, day, t))
+                .unwrap_or_default()
+        });
+        writeln!(out, "{:<name_width$}  {part1:>8}  {part2:>8}", member.name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stand_in;
+
+    const LEADERBOARD: &str = include_str!("../fixtures/leaderboard-2023.json");
+
+    #[test]
+    fn can_parse_leaderboard() {
+        let members = parse(LEADERBOARD).unwrap();
+        let names: Vec<_> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Ruth", "Kenton", "(anonymous user #1003)", "Lurker"]
+        );
+        assert_eq!(members[1].local_score, 26);
+        assert_eq!(members[1].days[&3], [Some(1701586402), None]);
+        assert_eq!(members[3].last_star, None);
+        assert!(parse("<!DOCTYPE html>").is_err());
+        assert!(parse("{}").is_err());
+    }
+
+    #[test]
+    fn can_write_table() {
+        let mut out = termcolor::Buffer::no_color();
+        write_table(&mut out, &parse(LEADERBOARD).unwrap()).unwrap();
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            "     Score  123  Name                    Stars  Last star\n  \
+             1)    27  ***  Ruth                        6  Dec 03 00:41:52\n  \
+             2)    26  ***  Kenton                      5  Dec 03 01:53:22\n  \
+             3)     3  *..  (anonymous user #1003)      1  Dec 01 14:00:00\n  \
+             4)     0  ...  Lurker                      0  \n"
+        );
+    }
+
+    #[test]
+    fn can_write_day() {
+        let mut out = Vec::new();
+        write_day(&mut out, &parse(LEADERBOARD).unwrap(), 2023, 1).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Name                      Part 1    Part 2\n\
+             Kenton                  00:05:52  00:26:31\n\
+             Ruth                    00:10:10  00:36:41\n\
+             (anonymous user #1003)  14:00:00          \n"
+        );
+    }
+
+    #[test]
+    fn cache_age_is_measured_from_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("leaderboard-1001.json");
+        assert_eq!(cache_age(&path), None);
+        std::fs::write(&path, LEADERBOARD).unwrap();
+        assert!(cache_age(&path).unwrap() < CACHE_FOR);
+    }
+
+    #[test]
+    fn can_fetch_leaderboard() {
+        let (base_url, server) = stand_in::serve_once("200 OK", "application/json", LEADERBOARD);
+
+        let json = fetch_leaderboard(&base_url, "abc123", 2023, "1001").unwrap();
+        assert_eq!(json, LEADERBOARD);
+        assert_eq!(
+            server.join().unwrap().line,
+            "GET /2023/leaderboard/private/view/1001.json HTTP/1.1"
+        );
+    }
+}
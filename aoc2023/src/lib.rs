@@ -1,4 +1,12 @@
+mod answer;
 pub mod grid;
+pub mod memory;
+mod parse_error;
+mod solution;
+
+pub use answer::Answer;
+pub use parse_error::{parse_number, ParseError};
+pub use solution::{Puzzle, Run, Solution, Timed};
 
 pub mod day01;
 pub mod day02;
@@ -17,3 +25,64 @@ pub mod day16;
 pub mod day17;
 pub mod day18;
 pub mod day19;
+
+/// Every implemented day, ordered by year and then day. Adding a new day only
+/// requires adding it here.
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+];
+
+/// Looks up the registered solution for the given day.
+pub fn solution(year: u32, day: u32) -> Option<&'static dyn Puzzle> {
+    SOLUTIONS
+        .iter()
+        .copied()
+        .find(|s| s.year() == year && s.day() == day)
+}
+
+/// Every year with at least one registered solution, in order.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = SOLUTIONS.iter().map(|s| s.year()).collect();
+    years.dedup();
+    years
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry_is_ordered_and_unique() {
+        let days: Vec<(u32, u32)> = SOLUTIONS.iter().map(|s| (s.year(), s.day())).collect();
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{days:?}");
+        assert!(days.iter().all(|(_, d)| (1..=25).contains(d)), "{days:?}");
+    }
+
+    #[test]
+    fn registry_lookup() {
+        assert_eq!(solution(2023, 1).map(|s| s.title()), Some("Trebuchet?!"));
+        // neither can ever be scaffolded
+        assert!(solution(2023, 26).is_none());
+        assert!(solution(1999, 1).is_none());
+
+        let years = years();
+        assert!(years.windows(2).all(|w| w[0] < w[1]), "{years:?}");
+        assert!(years.contains(&2023), "{years:?}");
+    }
+}
@@ -1,166 +1,488 @@
-use anyhow::{Context, Result};
-use aoc2023::*;
-use rayon::prelude::*;
-use std::io::Write;
+use answers::KnownAnswers;
+use anyhow::{bail, Context, Result};
+use budgets::Budgets;
+use cli::{BenchArgs, Cli, Command, Days, Format, Headless, Part, RunArgs};
+use std::io::{IsTerminal, Read, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+mod all_inputs;
+mod answers;
+mod bench;
+mod budgets;
+mod cli;
 mod inputs;
+mod leaderboard;
+mod puzzle;
+mod report;
+mod runner;
+mod samples;
+mod scaffold;
+mod submit;
+mod wait;
+mod watch;
 
-#[derive(Copy, Clone)]
-enum Status {
-    Done,
-    Pending,
-    Future,
+#[cfg(test)]
+mod stand_in;
+
+/// Only counts allocations once asked to by `run --memory`.
+#[global_allocator]
+static ALLOCATOR: aoc2023::memory::Counting = aoc2023::memory::Counting;
+
+fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    let cli = Cli::parse_args();
+    let years = cli.years();
+    let headless = cli.headless;
+    match cli.into_command() {
+        Command::Run(args) => run(&years, args, headless),
+        Command::Download { days, refresh } => {
+            download(&years, Days::or_all(days), refresh, headless)
+        }
+        Command::Submit { day, part, answer } => {
+            submit(single_year(&years)?, day, part, answer, headless)
+        }
+        Command::Bench(args) => bench(&years, args),
+        Command::Test { days, timeout } => test(&years, Days::or_all(days), timeout, headless),
+        Command::Read {
+            day,
+            markdown,
+            refresh,
+        } => read(single_year(&years)?, day, markdown, refresh, headless),
+        Command::New { day } => new(single_year(&years)?, day, headless),
+        Command::Watch { day } => watch::watch(single_year(&years)?, day, headless),
+        Command::Leaderboard { id, day } => {
+            show_leaderboard(single_year(&years)?, &id, day, headless)
+        }
+        Command::Wait { day } => wait(single_year(&years)?, day, headless),
+        Command::List => list(&years),
+    }
+}
+
+fn single_year(years: &[u32]) -> Result<u32> {
+    match years {
+        [year] => Ok(*year),
+        _ => bail!("Only one --year can be given here"),
+    }
+}
+
+fn load_inputs(years: &[u32], days: &[u32], headless: Headless) -> Result<()> {
+    for &year in years {
+        inputs::load_inputs(year, days, headless).with_context(|| "Failed to load inputs")?;
+    }
+    Ok(())
 }
 
-impl Status {
-    fn color(&self) -> Color {
-        match self {
-            Status::Done => Color::Green,
-            Status::Pending => Color::Yellow,
-            Status::Future => Color::White,
+fn run(years: &[u32], mut args: RunArgs, headless: Headless) -> Result<()> {
+    runner::use_threads(args.scheduling.threads)?;
+    if args.memory {
+        aoc2023::memory::start_counting();
+        args.scheduling.sequential = true;
+    }
+    if args.all_inputs {
+        return run_all_inputs(years, args);
+    }
+    let days = Days::or_all(args.days);
+    let budgets = Budgets::load(&args.budgets)?;
+    let (results, regressions) = match &args.input[..] {
+        [] => {
+            load_inputs(years, &days, headless)?;
+            let mut results =
+                runner::run_days(years, &days, args.part, args.timeout, args.scheduling);
+
+            // known answers only apply to our own inputs
+            let mut known = KnownAnswers::load(&args.answers)?;
+            let regressions = known.check(&mut results);
+            if args.record {
+                record_answers(&mut known, &results)?;
+            }
+            (results, regressions)
+        }
+        paths => {
+            let [day] = days[..] else {
+                bail!("--input can only be used with a single day");
+            };
+            let year = single_year(years)?;
+            let inputs = read_inputs(paths)?
+                .into_iter()
+                .map(|(name, input)| (year, day, name, input))
+                .collect();
+            let results =
+                runner::run_named_inputs(inputs, args.part, args.timeout, args.scheduling);
+            (results, Vec::new())
+        }
+    };
+    report::print(&results, args.format)?;
+
+    let failures: Vec<_> = results.iter().filter_map(|r| r.failure()).collect();
+    for failure in failures.iter() {
+        eprintln!();
+        eprintln!("{failure}");
+    }
+    if !regressions.is_empty() {
+        eprintln!();
+        for regression in regressions.iter() {
+            eprintln!("{regression}");
+        }
+    }
+    let exceeded = budgets.check(&results);
+    if !exceeded.is_empty() {
+        eprintln!();
+        eprintln!("Over budget:");
+        for exceeded in exceeded.iter() {
+            eprintln!("    {exceeded}");
         }
     }
+    let failed: Vec<_> = [
+        (failures.len(), "day(s) failed to run"),
+        (regressions.len(), "answer(s) regressed"),
+        (exceeded.len(), "time budget(s) exceeded"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, what)| format!("{count} {what}"))
+    .collect();
+    if !failed.is_empty() {
+        bail!("{}", failed.join(" and "));
+    }
+    Ok(())
 }
 
-impl ToString for Status {
-    fn to_string(&self) -> String {
-        match self {
-            Status::Done => "✓".to_string(),
-            Status::Pending => "…".to_string(),
-            Status::Future => " ".to_string(),
+/// Runs every day against each of its extra inputs, summarising which of them
+/// pass, fail or panic.
+fn run_all_inputs(years: &[u32], args: RunArgs) -> Result<()> {
+    let days = Days::or_all(args.days);
+    let mut inputs = Vec::new();
+    let mut expected = Vec::new();
+    for &year in years {
+        for &day in days.iter() {
+            if aoc2023::solution(year, day).is_none() {
+                continue;
+            }
+            for input in all_inputs::load(year, day)? {
+                inputs.push((year, day, input.name, input.input));
+                expected.push(input.expected);
+            }
         }
     }
+    if inputs.is_empty() {
+        bail!("There aren't any inputs in inputs/YYYY/dayNN/ for those days");
+    }
+
+    let mut results = runner::run_named_inputs(inputs, args.part, args.timeout, args.scheduling);
+    let outcomes: Vec<_> = results
+        .iter_mut()
+        .zip(expected.iter())
+        .map(|(result, expected)| all_inputs::check(result, expected))
+        .collect();
+    report::print(&results, args.format)?;
+
+    let (lines, summary) = all_inputs::summarise(&results, &outcomes, &expected);
+    eprintln!();
+    for line in lines.iter() {
+        eprintln!("{line}");
+    }
+    eprintln!("{summary}");
+    let failed = outcomes
+        .iter()
+        .filter(|o| {
+            matches!(
+                o,
+                all_inputs::Outcome::Failed | all_inputs::Outcome::Panicked
+            )
+        })
+        .count();
+    if failed > 0 {
+        bail!("{failed} input(s) didn't pass");
+    }
+    Ok(())
 }
 
-fn main() -> Result<()> {
-    dotenv::dotenv().ok();
-    inputs::load_inputs().with_context(|| "Failed to load inputs")?;
+/// Reads each of the given inputs, with `-` meaning stdin, along with a name
+/// to show it by.
+fn read_inputs(paths: &[PathBuf]) -> Result<Vec<(String, String)>> {
+    if paths.iter().filter(|p| p.as_os_str() == "-").count() > 1 {
+        bail!("stdin can only be read once");
+    }
+    paths
+        .iter()
+        .map(|path| {
+            if path.as_os_str() == "-" {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read input from stdin")?;
+                Ok(("stdin".to_string(), input))
+            } else {
+                let input = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read input from {}", path.display()))?;
+                Ok((path.display().to_string(), input))
+            }
+        })
+        .collect()
+}
 
-    let days: Vec<usize> = if let Some(day) = std::env::args().skip(1).next() {
-        vec![day
-            .parse()
-            .with_context(|| format!("Failed to parse day: {}", day))?]
-    } else {
-        (1..=25).collect()
+fn record_answers(known: &mut KnownAnswers, results: &[runner::DayResult]) -> Result<()> {
+    let mut recorded = 0;
+    for result in results.iter() {
+        for (part, part_result) in [(Part::One, &result.part1), (Part::Two, &result.part2)] {
+            if let Some(answer) = &part_result.answer {
+                if known.record(result.year, result.day, part, answer) {
+                    recorded += 1;
+                }
+            }
+        }
+    }
+    if recorded > 0 {
+        known.save()?;
+        eprintln!("Recorded {recorded} new answer(s)");
+    }
+    Ok(())
+}
+
+fn download(years: &[u32], days: Vec<u32>, refresh: bool, headless: Headless) -> Result<()> {
+    for &year in years {
+        let mut missing = inputs::check_missing_inputs(year, &days);
+        if refresh {
+            for (day, problem) in inputs::check_corrupted_inputs(year, &days) {
+                println!("{year} day {day}: {problem}");
+                missing.push(day);
+            }
+            missing.sort();
+        }
+        if missing.is_empty() {
+            println!("All available {year} inputs have already been downloaded.");
+            continue;
+        }
+        inputs::download_inputs(year, &missing, headless)?;
+    }
+    Ok(())
+}
+
+fn submit(
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: Option<String>,
+    headless: Headless,
+) -> Result<()> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            inputs::load_inputs(year, &[day], headless).with_context(|| "Failed to load inputs")?;
+            let result = runner::run_day(year, day, Some(part), runner::DEFAULT_TIMEOUT);
+            if let Some(failure) = result.failure() {
+                bail!("{failure}");
+            }
+            let result = match part {
+                Part::One => result.part1,
+                Part::Two => result.part2,
+            };
+            match result.answer {
+                Some(answer) => answer.to_string(),
+                None => bail!("{year} day {day} doesn't have an answer for that part yet"),
+            }
+        }
+    };
+    let outcome = submit::submit(year, day, part, &answer, headless)?;
+    if outcome == submit::Outcome::Correct {
+        let mut known = KnownAnswers::load(answers::DEFAULT_PATH)?;
+        if known.record(year, day, part, &answer.as_str().into()) {
+            known.save()?;
+        }
+        if part == Part::One && puzzle::puzzle_path(year, day).exists() {
+            // part 2 has just unlocked
+            match puzzle::load_puzzle(year, day, true, headless) {
+                Ok(Some(html)) => refresh_samples(year, day, &html),
+                Ok(None) => {}
+                Err(e) => eprintln!("Failed to download part 2 of {year} day {day}: {e:#}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bench(years: &[u32], args: BenchArgs) -> Result<()> {
+    let scheduling = args.scheduling();
+    let days = Days::or_all(args.days);
+    if args.runs == 0 {
+        bail!("--runs must be at least 1");
+    }
+    runner::use_threads(scheduling.threads)?;
+
+    let record = bench::Record {
+        revision: bench::revision(),
+        timestamp: chrono::Utc::now(),
+        runs: args.runs,
+        days: bench::bench(years, &days, args.part, args.runs, scheduling),
     };
+    bench::print(&record, args.format)?;
 
-    let results: Vec<((Option<usize>, Option<usize>), (Status, Status), f64)> = days
-        .par_iter()
-        .map(|day| {
-            let input =
-                std::fs::read_to_string(PathBuf::from("inputs").join(format!("day{:02}.txt", day)))
-                    .ok();
-            let has_input = input.is_some();
-            let now = std::time::Instant::now();
-            let res = match (day, input) {
-                (1, Some(input)) => (day01::run(&input), true),
-                (2, Some(input)) => (day02::run(&input), true),
-                (3, Some(input)) => (day03::run(&input), true),
-                (4, Some(input)) => (day04::run(&input), true),
-                (5, Some(input)) => (day05::run(&input), true),
-                (6, Some(input)) => (day06::run(&input), true),
-                (7, Some(input)) => (day07::run(&input), true),
-                (8, Some(input)) => (day08::run(&input), true),
-                (9, Some(input)) => (day09::run(&input), true),
-                (10, Some(input)) => (day10::run(&input), true),
-                (11, Some(input)) => (day11::run(&input), true),
-                (14, Some(input)) => (day14::run(&input), true),
-                (15, Some(input)) => (day15::run(&input), true),
-                (16, Some(input)) => (day16::run(&input), true),
-                (17, Some(input)) => (day17::run(&input), true),
-                (18, Some(input)) => (day18::run(&input), true),
-                (19, Some(input)) => (day19::run(&input), true),
-                _ => ((None, None), has_input),
+    let previous = bench::load_previous(&args.history)?;
+    bench::append(&args.history, &record)?;
+    if let Some(previous) = previous {
+        let changes = bench::compare(&previous, &record, args.threshold);
+        eprintln!();
+        bench::print_changes(&previous, &changes, args.threshold)?;
+    }
+    Ok(())
+}
+
+fn test(years: &[u32], days: Vec<u32>, timeout: Duration, headless: Headless) -> Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    let (mut passed, mut failed) = (0, 0);
+    for &year in years {
+        for &day in days.iter() {
+            if aoc2023::solution(year, day).is_none() {
+                continue;
+            }
+            let samples = match samples::load_samples(year, day, headless) {
+                Ok(Some(samples)) => samples,
+                Ok(None) => {
+                    eprintln!("Skipping {year} day {day}: no samples downloaded");
+                    continue;
+                }
+                Err(e) => {
+                    // the other days' samples are still worth checking
+                    failed += 1;
+                    eprintln!("Failed to load samples for {year} day {day}: {e:#}");
+                    continue;
+                }
             };
-            (res, now.elapsed().as_secs_f64())
-        })
-        .map(|day| match day {
-            (((p1, p2), true), elapsed) => (
-                (p1, p2),
-                match (p1.is_some(), p2.is_some()) {
-                    (true, true) => (Status::Done, Status::Done),
-                    (true, false) => (Status::Done, Status::Pending),
-                    (false, true) => (Status::Pending, Status::Pending),
-                    (false, false) => (Status::Pending, Status::Pending),
-                },
-                elapsed,
-            ),
-            (((p1, p2), false), elapsed) => ((p1, p2), (Status::Future, Status::Future), elapsed),
-        })
-        .collect();
+            for sample in samples.iter() {
+                let result = runner::run_sample(year, day, sample, timeout);
+                let part = match sample.part {
+                    Part::One => &result.part1,
+                    Part::Two => &result.part2,
+                };
+                if let Some(failure) = result.failure() {
+                    failed += 1;
+                    write!(stdout, "{year} day {day} part {}: ", sample.part)?;
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+                    write!(stdout, "{} ✗", part.status.name().to_lowercase())?;
+                    stdout.reset()?;
+                    writeln!(stdout, "\n{failure}")?;
+                    continue;
+                }
+                let Some(answer) = &part.answer else {
+                    continue;
+                };
+                let answer = answer.to_string();
+                write!(stdout, "{year} day {day} part {}: ", sample.part)?;
+                if answer == sample.answer {
+                    passed += 1;
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Green)))?;
+                    write!(stdout, "{answer} ✓")?;
+                } else {
+                    failed += 1;
+                    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+                    write!(stdout, "expected {}, got {answer} ✗", sample.answer)?;
+                }
+                stdout.reset()?;
+                writeln!(stdout)?;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} of {} sample(s) failed", passed + failed);
+    }
+    Ok(())
+}
+
+/// Picks up any new samples from a re-downloaded puzzle, which isn't worth
+/// failing over.
+fn refresh_samples(year: u32, day: u32, html: &str) {
+    if let Err(e) = samples::refresh_samples(year, day, html) {
+        eprintln!("Failed to update the samples for {year} day {day}: {e:#}");
+    }
+}
 
-    let results: Vec<((String, Status), (String, Status), String)> = results
-        .into_iter()
-        .map(|((p1, p2), (s1, s2), elapsed)| {
-            let p1 = p1
-                .map(|p1| p1.to_string())
-                .unwrap_or_else(|| s1.to_string());
-            let p2 = p2
-                .map(|p2| p2.to_string())
-                .unwrap_or_else(|| s2.to_string());
-            ((p1, s1), (p2, s2), format!("{:.6}s", elapsed))
+fn read(year: u32, day: u32, markdown: bool, refresh: bool, headless: Headless) -> Result<()> {
+    let Some(html) = puzzle::load_puzzle(year, day, refresh, headless)? else {
+        bail!("The puzzle for {year} day {day} hasn't been downloaded");
+    };
+    if refresh {
+        refresh_samples(year, day, &html);
+    }
+    let blocks = puzzle::parse_blocks(&puzzle::extract_articles(&html).join("\n"));
+    if markdown {
+        print!("{}", puzzle::to_markdown(&blocks));
+        return Ok(());
+    }
+    let mut text = if std::io::stdout().is_terminal() {
+        termcolor::Buffer::ansi()
+    } else {
+        termcolor::Buffer::no_color()
+    };
+    puzzle::write_text(&mut text, &blocks)?;
+    puzzle::page(text.as_slice())
+}
+
+fn new(year: u32, day: u32, headless: Headless) -> Result<()> {
+    // fill in what we can from the puzzle, but it isn't needed
+    let title = puzzle::load_puzzle(year, day, false, headless)
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load the puzzle for {year} day {day}: {e:#}");
+            None
         })
-        .collect();
+        .and_then(|html| puzzle::title(&html))
+        .unwrap_or_else(|| format!("Day {day}"));
+    let samples = samples::load_samples(year, day, headless)
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load samples for {year} day {day}: {e:#}");
+            None
+        })
+        .unwrap_or_default();
 
-    let (p1_width, p2_width) =
-        results
-            .iter()
-            .fold((0, 0), |(p1_width, p2_width), ((p1, _), (p2, _), _)| {
-                (p1_width.max(p1.len()), p2_width.max(p2.len()))
-            });
-    let p1_width = p1_width.max("Part 1".len());
-    let p2_width = p2_width.max("Part 2".len());
-
-    println!(
-        "╒═════╤═{p1:═<width1$}═╤═{p2:═<width2$}═╤═══════════╕",
-        p1 = "",
-        p2 = "",
-        width1 = p1_width,
-        width2 = p2_width
-    );
-    println!(
-        "│ Day │ {p1: <width1$}Part 1 │ {p2: <width2$}Part 2 │   Elapsed │",
-        p1 = "",
-        p2 = "",
-        width1 = (p1_width - "Part 1".len()),
-        width2 = (p2_width - "Part 2".len())
-    );
-    println!(
-        "├─────┼─{p1:─<width1$}─┼─{p2:─<width2$}─┼───────────┤",
-        p1 = "",
-        p2 = "",
-        width1 = p1_width,
-        width2 = p2_width
-    );
-    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
-    for (day, ((p1, s1), (p2, s2), elapsed)) in days.into_iter().zip(results.into_iter()) {
-        write!(stdout, "│ {day:>3} │ ")?;
-        stdout.set_color(ColorSpec::new().set_fg(Some(s1.color())))?;
-        write!(stdout, "{p1:>width1$}", p1 = p1, width1 = p1_width,)?;
-        stdout.reset()?;
-        write!(stdout, " │ ",)?;
-        stdout.set_color(ColorSpec::new().set_fg(Some(s2.color())))?;
-        write!(stdout, "{p2:>width2$}", p2 = p2, width2 = p2_width,)?;
-        write!(stdout, " │ ",)?;
-        if elapsed != "0.000000s" {
-            stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
-            write!(stdout, "{elapsed}")?;
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    for (path, changed) in scaffold::scaffold(root, year, day, &title, &samples)? {
+        if changed {
+            println!("{path}: written");
         } else {
-            stdout.set_color(ColorSpec::new().set_fg(None))?;
-            write!(stdout, "         ")?;
+            println!("{path}: left alone");
         }
-        stdout.reset()?;
-        writeln!(stdout, " │")?;
     }
-    println!(
-        "╘═════╧═{p1:═<width1$}═╧═{p2:═<width2$}═╧═══════════╛",
-        p1 = "",
-        p2 = "",
-        width1 = p1_width,
-        width2 = p2_width
-    );
+    Ok(())
+}
+
+fn show_leaderboard(year: u32, id: &str, day: Option<u32>, headless: Headless) -> Result<()> {
+    let (members, age) = leaderboard::load(year, id, headless)?;
+    if age > Duration::ZERO {
+        eprintln!(
+            "Fetched {} minute(s) ago, AoC asks that it's fetched at most every {} minutes",
+            age.as_secs() / 60,
+            leaderboard::CACHE_FOR.as_secs() / 60
+        );
+    }
+    match day {
+        Some(day) => leaderboard::write_day(&mut std::io::stdout().lock(), &members, year, day),
+        None => leaderboard::write_table(&mut StandardStream::stdout(ColorChoice::Auto), &members),
+    }
+}
 
+fn wait(year: u32, day: Option<u32>, headless: Headless) -> Result<()> {
+    let day = wait::wait_for_unlock(year, day, headless)?;
+    if aoc2023::solution(year, day).is_none() {
+        return new(year, day, headless);
+    }
+    let result = runner::run_day(year, day, None, runner::DEFAULT_TIMEOUT);
+    report::print(&[result], Format::Table)
+}
+
+fn list(years: &[u32]) -> Result<()> {
+    for &year in years {
+        println!("{year}");
+        for solution in aoc2023::SOLUTIONS.iter().filter(|s| s.year() == year) {
+            let day = solution.day();
+            let input = if inputs::input_path(year, day).exists() {
+                ""
+            } else {
+                " (no input)"
+            };
+            println!("{day:>3}  {}{input}", solution.title());
+        }
+    }
     Ok(())
 }
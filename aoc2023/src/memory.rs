@@ -0,0 +1,124 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+/// The system allocator, but counting how much is allocated once
+/// [`start_counting`] has been called. Install it with `#[global_allocator]`
+/// to have [`measure`] report anything.
+pub struct Counting;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+// signed, since memory allocated before counting started can be freed after
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn allocated(bytes: isize) {
+    if !COUNTING.load(Ordering::Relaxed) {
+        return;
+    }
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn freed(bytes: isize) {
+    if COUNTING.load(Ordering::Relaxed) {
+        CURRENT.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size() as isize);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // growing a `Vec` is as wasteful as allocating a new one
+            allocated(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting allocations. Until then, the allocator costs (almost)
+/// nothing over the system one.
+pub fn start_counting() {
+    COUNTING.store(true, Ordering::Relaxed);
+}
+
+pub fn is_counting() -> bool {
+    COUNTING.load(Ordering::Relaxed)
+}
+
+/// How much memory a stage of a solution used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// The most it had allocated at once, in bytes, on top of whatever was
+    /// allocated before it started.
+    pub peak: usize,
+    /// How many times it allocated (or grew an allocation).
+    pub allocations: usize,
+}
+
+/// Runs `f`, measuring how much memory it used. The counts are shared by
+/// every thread, so this is only accurate when nothing else is running.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let value = f();
+    let usage = Usage {
+        peak: (PEAK.load(Ordering::Relaxed) - start).max(0) as usize,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+    (value, usage)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measures_peak_usage_and_allocations() {
+        // not installed as the global allocator, so only these calls count
+        start_counting();
+        let small = Layout::from_size_align(1024, 8).unwrap();
+        let large = Layout::from_size_align(4096, 8).unwrap();
+        let ((), usage) = measure(|| unsafe {
+            let a = Counting.alloc(small);
+            let b = Counting.alloc_zeroed(small);
+            Counting.dealloc(a, small);
+            let b = Counting.realloc(b, small, large.size());
+            Counting.dealloc(b, large);
+        });
+        assert_eq!(
+            usage,
+            Usage {
+                peak: 4096,
+                allocations: 3
+            }
+        );
+
+        let ((), usage) = measure(|| ());
+        assert_eq!(usage, Usage::default());
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Where and why a puzzle input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input the error is on.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The raw text of the offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error at the given byte offset into `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            text: input[start..end].trim_end_matches('\r').to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error at `fragment`, which must be a slice of `input` (like a line,
+    /// token or whatever a nom parser had left over). Anything else is
+    /// reported at the start of the input.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(0);
+        Self::at_offset(input, offset, message)
+    }
+
+    /// An error at wherever a nom parser over `input` gave up.
+    pub fn from_nom(
+        input: &str,
+        error: nom::Err<nom::error::Error<&str>>,
+        message: impl Into<String>,
+    ) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::at(input, e.input, message),
+            nom::Err::Incomplete(_) => Self::at_offset(input, input.len(), message),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "  | {}", self.text)?;
+        write!(f, "  | {:>1$}", "^", self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, which must be a slice of `input`, pointing at it if it
+/// isn't a valid `T`.
+pub fn parse_number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, format!("expected a number, found '{token}'")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn points_at_fragments() {
+        let input = "1 2 3\n4 x 6\n";
+        let x = &input[8..9];
+        let error = ParseError::at(input, x, "expected a number");
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "4 x 6");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number\n  | 4 x 6\n  |   ^"
+        );
+
+        let error = ParseError::at(input, "elsewhere", "oops");
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = ParseError::at_offset(input, input.len(), "unexpected end");
+        assert_eq!((error.line, error.column, error.text.as_str()), (3, 1, ""));
+
+        let nom_error = nom::character::complete::digit1::<_, nom::error::Error<&str>>(&input[6..])
+            .and_then(|(rest, _)| nom::character::complete::digit1(&rest[1..]))
+            .unwrap_err();
+        let error = ParseError::from_nom(input, nom_error, "expected a number");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn can_parse_numbers() {
+        let input = "12 -3 4a";
+        assert_eq!(parse_number::<u32>(input, &input[0..2]), Ok(12));
+        assert_eq!(parse_number::<i64>(input, &input[3..5]), Ok(-3));
+        let error = parse_number::<u32>(input, &input[6..8]).unwrap_err();
+        assert_eq!(error.column, 7);
+        assert_eq!(error.message, "expected a number, found '4a'");
+    }
+}
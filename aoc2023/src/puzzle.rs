@@ -0,0 +1,371 @@
+use crate::cli::Headless;
+use crate::inputs;
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// Puzzle descriptions are kept next to the inputs, in `inputs/YYYY/dayNN.html`,
+/// so they can be read offline.
+pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
+    inputs::input_path(year, day).with_extension("html")
+}
+
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn strip_tags(text: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").expect("valid regex");
+    decode_entities(&tags.replace_all(text, ""))
+}
+
+/// Keeps just the puzzle's articles (part 1, and part 2 once it's unlocked),
+/// dropping the rest of the page.
+pub fn extract_articles(html: &str) -> Vec<&str> {
+    let article =
+        Regex::new(r#"(?s)<article class="day-desc">.*?</article>"#).expect("valid regex");
+    article.find_iter(html).map(|m| m.as_str()).collect()
+}
+
+/// The puzzle's title, from the `--- Day 1: Trebuchet?! ---` heading.
+pub fn title(html: &str) -> Option<String> {
+    let heading = Regex::new(r"<h2>--- Day \d+: (.*?) ---</h2>").expect("valid regex");
+    heading.captures(html).map(|c| decode_entities(&c[1]))
+}
+
+/// Downloads a puzzle's description, which includes part 2 once part 1 has
+/// been solved.
+pub fn fetch_puzzle(base_url: &str, session: &str, year: u32, day: u32) -> Result<String> {
+    let url = format!("{base_url}/{year}/day/{day}");
+    inputs::fetch_page(&url, session, &format!("{year} day {day}'s puzzle"))
+}
+
+pub fn download_puzzle(year: u32, day: u32, session: &str) -> Result<String> {
+    let html = fetch_puzzle(&inputs::base_url(), session, year, day)?;
+    let articles = extract_articles(&html);
+    if articles.is_empty() {
+        bail!("Couldn't find the puzzle description for {year} day {day}");
+    }
+    let articles = articles.join("\n");
+    let path = puzzle_path(year, day);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create inputs directory: {}", dir.display()))?;
+    }
+    std::fs::write(&path, &articles).with_context(|| {
+        format!(
+            "Failed to write puzzle for {year} day {day} to file: {}",
+            path.display()
+        )
+    })?;
+    Ok(articles)
+}
+
+/// Loads a puzzle's description, downloading it if it hasn't been yet (or
+/// `refresh` is set, to pick up part 2). Returns `None` if it can't be
+/// downloaded.
+pub fn load_puzzle(
+    year: u32,
+    day: u32,
+    refresh: bool,
+    headless: Headless,
+) -> Result<Option<String>> {
+    let path = puzzle_path(year, day);
+    if path.exists() && !refresh {
+        return std::fs::read_to_string(&path)
+            .map(Some)
+            .with_context(|| format!("Failed to read puzzle from {}", path.display()));
+    }
+    if !headless.can_download() {
+        return Ok(None);
+    }
+    let session =
+        inputs::get_session(headless).with_context(|| "Failed to get AOC session auth")?;
+    download_puzzle(year, day, &session).map(Some)
+}
+
+/// A run of text with the same formatting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub emphasis: bool,
+    pub code: bool,
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Heading(String),
+    Paragraph(Vec<Span>),
+    Code(String),
+    List(Vec<Vec<Span>>),
+}
+
+/// Breaks a puzzle description into blocks of formatted text. Only the
+/// handful of elements that AoC uses are understood; anything else is
+/// treated as plain text.
+pub fn parse_blocks(html: &str) -> Vec<Block> {
+    let token = Regex::new(r#"(?s)<(/?)(\w+)([^>]*)>|([^<]+)"#).expect("valid regex");
+    let href = Regex::new(r#"href="([^"]*)""#).expect("valid regex");
+
+    let mut blocks = Vec::new();
+    let mut spans: Vec<Span> = Vec::new();
+    let mut items: Vec<Vec<Span>> = Vec::new();
+    let mut code_block: Option<String> = None;
+    let (mut emphasis, mut code) = (false, false);
+    let mut link: Option<String> = None;
+
+    for captures in token.captures_iter(html) {
+        if let Some(text) = captures.get(4) {
+            let text = decode_entities(text.as_str());
+            match code_block.as_mut() {
+                Some(block) => block.push_str(&text),
+                None => spans.push(Span {
+                    text,
+                    emphasis,
+                    code,
+                    link: link.clone(),
+                }),
+            }
+            continue;
+        }
+        let closing = &captures[1] == "/";
+        match (&captures[2], closing) {
+            ("pre", false) => code_block = Some(String::new()),
+            ("pre", true) => blocks.extend(code_block.take().map(Block::Code)),
+            ("h2", true) => {
+                let text = spans.drain(..).map(|s| s.text).collect::<String>();
+                blocks.push(Block::Heading(text.trim().to_string()));
+            }
+            ("p", true) => blocks.push(Block::Paragraph(std::mem::take(&mut spans))),
+            ("li", true) => items.push(std::mem::take(&mut spans)),
+            ("ul", true) => blocks.push(Block::List(std::mem::take(&mut items))),
+            ("em", _) => emphasis = !closing,
+            ("code", _) => code = !closing,
+            ("a", false) => link = href.captures(&captures[3]).map(|c| c[1].to_string()),
+            ("a", true) => link = None,
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Paragraphs are wrapped in the HTML, so collapse their whitespace the way a
+/// browser would.
+fn collapse_whitespace(spans: &[Span]) -> Vec<Span> {
+    let mut collapsed: Vec<Span> = Vec::new();
+    let mut space = true;
+    for span in spans.iter() {
+        let mut text = String::new();
+        for c in span.text.chars() {
+            if c.is_whitespace() {
+                if !space {
+                    text.push(' ');
+                }
+                space = true;
+            } else {
+                text.push(c);
+                space = false;
+            }
+        }
+        if !text.is_empty() {
+            collapsed.push(Span {
+                text,
+                ..span.clone()
+            });
+        }
+    }
+    if let Some(last) = collapsed.last_mut() {
+        last.text.truncate(last.text.trim_end().len());
+    }
+    collapsed
+}
+
+fn span_markdown(span: &Span) -> String {
+    let mut text = span.text.clone();
+    if span.code {
+        text = format!("`{text}`");
+    }
+    if span.emphasis {
+        text = format!("*{text}*");
+    }
+    if let Some(link) = &span.link {
+        text = format!("[{text}]({link})");
+    }
+    text
+}
+
+pub fn to_markdown(blocks: &[Block]) -> String {
+    let blocks: Vec<String> = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading(text) => format!("## {text}"),
+            Block::Paragraph(spans) => collapse_whitespace(spans)
+                .iter()
+                .map(span_markdown)
+                .collect(),
+            Block::Code(code) => format!("```\n{}\n```", code.trim_end_matches('\n')),
+            Block::List(items) => items
+                .iter()
+                .map(|item| {
+                    let item: String = collapse_whitespace(item)
+                        .iter()
+                        .map(span_markdown)
+                        .collect();
+                    format!("- {item}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        })
+        .collect();
+    blocks.join("\n\n") + "\n"
+}
+
+fn write_spans<W: WriteColor>(out: &mut W, spans: &[Span]) -> Result<()> {
+    for span in collapse_whitespace(spans).iter() {
+        let mut color = ColorSpec::new();
+        if span.code {
+            color.set_fg(Some(Color::Cyan));
+        }
+        if span.emphasis {
+            color.set_fg(Some(Color::Yellow)).set_bold(true);
+        }
+        color.set_underline(span.link.is_some());
+        out.set_color(&color)?;
+        write!(out, "{}", span.text)?;
+        out.reset()?;
+    }
+    Ok(())
+}
+
+/// Writes the puzzle as text for reading in a terminal, with emphasis and code
+/// picked out in colour.
+pub fn write_text<W: WriteColor>(out: &mut W, blocks: &[Block]) -> Result<()> {
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        match block {
+            Block::Heading(text) => {
+                out.set_color(ColorSpec::new().set_fg(Some(Color::Green)).set_bold(true))?;
+                write!(out, "{text}")?;
+                out.reset()?;
+                writeln!(out)?;
+            }
+            Block::Paragraph(spans) => {
+                write_spans(out, spans)?;
+                writeln!(out)?;
+            }
+            Block::Code(code) => {
+                out.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+                for line in code.trim_end_matches('\n').lines() {
+                    writeln!(out, "    {line}")?;
+                }
+                out.reset()?;
+            }
+            Block::List(items) => {
+                for item in items.iter() {
+                    write!(out, "  • ")?;
+                    write_spans(out, item)?;
+                    writeln!(out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shows the text through `$PAGER` (or `less`) when writing to a terminal,
+/// falling back to printing it if that doesn't work out.
+pub fn page(text: &[u8]) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if stdout.is_terminal() {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+        let mut args = pager.split_whitespace();
+        if let Some(program) = args.next() {
+            let child = std::process::Command::new(program)
+                .args(args)
+                .stdin(std::process::Stdio::piped())
+                .spawn();
+            if let Ok(mut child) = child {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // the pager closing early isn't an error
+                    stdin.write_all(text).ok();
+                }
+                child.wait().context("Failed to wait for pager")?;
+                return Ok(());
+            }
+        }
+    }
+    stdout.write_all(text)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stand_in;
+
+    const PUZZLE: &str = include_str!("../fixtures/puzzle-2023-day01.html");
+
+    #[test]
+    fn can_extract_articles() {
+        let articles = extract_articles(PUZZLE);
+        assert_eq!(articles.len(), 2);
+        assert!(articles[0].starts_with("<article class=\"day-desc\"><h2>--- Day 1"));
+        assert!(articles[1].contains("--- Part Two ---"));
+        assert!(!articles.join("\n").contains("Your puzzle answer was"));
+        assert_eq!(title(PUZZLE).as_deref(), Some("Trebuchet?!"));
+    }
+
+    #[test]
+    fn can_convert_to_markdown() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with
+<a href="/2023/events">global snow production</a>, and you've been selected to take a look.</p>
+<ul>
+<li>Each <em>line</em> has two digits.</li>
+<li>The first is <code>&lt;1&gt;</code>.</li>
+</ul>
+<pre><code>1abc2
+<em>pqr3</em>stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>"#;
+        assert_eq!(
+            to_markdown(&parse_blocks(html)),
+            "## --- Day 1: Trebuchet?! ---\n\n\
+             Something is wrong with [global snow production](/2023/events), and you've been selected to take a look.\n\n\
+             - Each *line* has two digits.\n\
+             - The first is `<1>`.\n\n\
+             ```\n1abc2\npqr3stu8vwx\n```\n\n\
+             Adding these together produces *`142`*.\n"
+        );
+    }
+
+    #[test]
+    fn can_write_text() {
+        let blocks = parse_blocks(&extract_articles(PUZZLE).join("\n"));
+        let mut out = termcolor::Buffer::no_color();
+        write_text(&mut out, &blocks).unwrap();
+        let text = String::from_utf8(out.into_inner()).unwrap();
+        assert!(text.starts_with("--- Day 1: Trebuchet?! ---\n\n"));
+        assert!(text.contains("\n    1abc2\n    pqr3stu8vwx\n"));
+        assert!(text.contains("Adding these together produces 142.\n"));
+        assert!(text.contains("--- Part Two ---"));
+    }
+
+    #[test]
+    fn can_fetch_puzzle() {
+        let (base_url, server) = stand_in::serve_once("200 OK", "text/html", PUZZLE);
+
+        let html = fetch_puzzle(&base_url, "abc123", 2023, 1).unwrap();
+        assert_eq!(extract_articles(&html).len(), 2);
+        assert_eq!(server.join().unwrap().line, "GET /2023/day/1 HTTP/1.1");
+    }
+}
@@ -0,0 +1,317 @@
+use crate::cli::Format;
+use crate::runner::{self, DayResult, PartResult};
+use anyhow::Result;
+use aoc2023::memory::Usage;
+use serde_json::json;
+use std::io::Write;
+
+/// Prints the results in the given format, so they can be fed into other
+/// tools (or pasted into the README).
+pub fn print(results: &[DayResult], format: Format) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    match format {
+        Format::Json => write_json(&mut stdout, results),
+        Format::Csv => write_csv(&mut stdout, results),
+        Format::Table | Format::Markdown => {
+            // one table per year, with a heading if there's more than one
+            let years: Vec<_> = results.chunk_by(|a, b| a.year == b.year).collect();
+            for (i, year) in years.iter().enumerate() {
+                if years.len() > 1 {
+                    if i > 0 {
+                        writeln!(stdout)?;
+                    }
+                    match format {
+                        Format::Markdown => writeln!(stdout, "## {}\n", year[0].year)?,
+                        _ => writeln!(stdout, "{}", year[0].year)?,
+                    }
+                }
+                match format {
+                    Format::Markdown => write_markdown(&mut stdout, year)?,
+                    _ => {
+                        stdout.flush()?;
+                        runner::print_table(year)?
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn memory_json(memory: Option<Usage>) -> serde_json::Value {
+    match memory {
+        Some(memory) => json!({
+            "peak_bytes": memory.peak,
+            "allocations": memory.allocations,
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+fn part_json(part: &PartResult) -> serde_json::Value {
+    json!({
+        "answer": part.answer.as_ref().map(|a| a.to_string()),
+        "status": part.status.name(),
+        "elapsed": part.elapsed,
+        "memory": memory_json(part.memory),
+    })
+}
+
+pub fn write_json<W: Write>(out: &mut W, results: &[DayResult]) -> Result<()> {
+    let days: Vec<_> = results
+        .iter()
+        .map(|r| {
+            json!({
+                "year": r.year,
+                "day": r.day,
+                "input": r.input,
+                "part1": part_json(&r.part1),
+                "part2": part_json(&r.part2),
+                "parse": r.parse,
+                "parse_memory": memory_json(r.parse_memory),
+                "elapsed": r.elapsed(),
+                "error": r.error,
+            })
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut *out, &days)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn write_csv<W: Write>(out: &mut W, results: &[DayResult]) -> Result<()> {
+    writeln!(
+        out,
+        "year,day,input,part1,part1_status,part2,part2_status,parse_seconds,part1_seconds,part2_seconds,elapsed_seconds,\
+         parse_peak_bytes,parse_allocations,part1_peak_bytes,part1_allocations,part2_peak_bytes,part2_allocations"
+    )?;
+    for r in results.iter() {
+        let answer = |p: &PartResult| {
+            p.answer
+                .as_ref()
+                .map(|a| csv_field(&a.to_string()))
+                .unwrap_or_default()
+        };
+        // left empty when allocations weren't counted
+        let memory = [r.parse_memory, r.part1.memory, r.part2.memory]
+            .iter()
+            .map(|m| match m {
+                Some(m) => format!("{},{}", m.peak, m.allocations),
+                None => ",".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{}",
+            r.year,
+            r.day,
+            r.input.as_deref().map(csv_field).unwrap_or_default(),
+            answer(&r.part1),
+            r.part1.status.name(),
+            answer(&r.part2),
+            r.part2.status.name(),
+            r.parse,
+            r.part1.elapsed,
+            r.part2.elapsed,
+            r.elapsed(),
+            memory
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_markdown<W: Write>(out: &mut W, results: &[DayResult]) -> Result<()> {
+    let memory = results.iter().any(|r| r.parse_memory.is_some());
+    let (memory_headings, memory_rule) = match memory {
+        true => (
+            " Parse Memory | Part 1 Memory | Part 2 Memory |",
+            "-------------:|--------------:|--------------:|",
+        ),
+        false => ("", ""),
+    };
+    // as in the table, inputs only get a column when they aren't the usual ones
+    let inputs = results.iter().any(|r| r.input.is_some());
+    let (input_heading, input_rule) = match inputs {
+        true => (" Input |", ":------|"),
+        false => ("", ""),
+    };
+    writeln!(
+        out,
+        "| Day |{input_heading} Part 1 | Part 2 | Parse | Part 1 Time | Part 2 Time |{memory_headings}"
+    )?;
+    writeln!(
+        out,
+        "|----:|{input_rule}-------:|-------:|------:|------------:|------------:|{memory_rule}"
+    )?;
+    for r in results.iter() {
+        // parts without an answer show their status instead, and answers
+        // show whether they matched the known one
+        let cell = |p: &PartResult| {
+            p.answer
+                .as_ref()
+                .map(|a| format!("{} `{a}`", p.status.symbol()))
+                .unwrap_or_else(|| p.status.name().to_string())
+        };
+        write!(out, "| {} |", r.day)?;
+        if inputs {
+            let input = r.input.as_deref().unwrap_or_default();
+            write!(out, " {} |", input.replace('|', "\\|"))?;
+        }
+        write!(
+            out,
+            " {} | {} | {:.6}s | {:.6}s | {:.6}s |",
+            cell(&r.part1),
+            cell(&r.part2),
+            r.parse,
+            r.part1.elapsed,
+            r.part2.elapsed
+        )?;
+        if memory {
+            for usage in [r.parse_memory, r.part1.memory, r.part2.memory] {
+                write!(out, " {} |", runner::format_memory(usage))?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::Status;
+    use aoc2023::Answer;
+
+    fn results() -> Vec<DayResult> {
+        vec![
+            DayResult {
+                year: 2023,
+                day: 1,
+                input: None,
+                part1: PartResult {
+                    answer: Some(Answer::from(142usize)),
+                    status: Status::Done,
+                    elapsed: 0.125,
+                    memory: Some(Usage {
+                        peak: 1536,
+                        allocations: 12,
+                    }),
+                },
+                part2: PartResult {
+                    answer: Some(Answer::from("a,b")),
+                    status: Status::Regressed,
+                    elapsed: 0.5,
+                    memory: Some(Usage {
+                        peak: 3 << 20,
+                        allocations: 4000,
+                    }),
+                },
+                parse: 0.25,
+                parse_memory: Some(Usage {
+                    peak: 512,
+                    allocations: 1,
+                }),
+                error: None,
+            },
+            DayResult {
+                year: 2023,
+                day: 2,
+                input: Some("edge, case.txt".to_string()),
+                part1: PartResult {
+                    answer: None,
+                    status: Status::InputError,
+                    elapsed: 0.0,
+                    memory: None,
+                },
+                part2: PartResult {
+                    answer: None,
+                    status: Status::InputError,
+                    elapsed: 0.0,
+                    memory: None,
+                },
+                parse: 0.0,
+                parse_memory: None,
+                error: Some("line 1, column 1: expected a number".to_string()),
+            },
+        ]
+    }
+
+    fn render(write: fn(&mut Vec<u8>, &[DayResult]) -> Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&mut out, &results()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn can_write_json() {
+        let value: serde_json::Value = serde_json::from_str(&render(write_json)).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                {
+                    "year": 2023,
+                    "day": 1,
+                    "input": null,
+                    "part1": {
+                        "answer": "142",
+                        "status": "Done",
+                        "elapsed": 0.125,
+                        "memory": { "peak_bytes": 1536, "allocations": 12 },
+                    },
+                    "part2": {
+                        "answer": "a,b",
+                        "status": "Regressed",
+                        "elapsed": 0.5,
+                        "memory": { "peak_bytes": 3145728, "allocations": 4000 },
+                    },
+                    "parse": 0.25,
+                    "parse_memory": { "peak_bytes": 512, "allocations": 1 },
+                    "elapsed": 0.875,
+                    "error": null,
+                },
+                {
+                    "year": 2023,
+                    "day": 2,
+                    "input": "edge, case.txt",
+                    "part1": { "answer": null, "status": "Input error", "elapsed": 0.0, "memory": null },
+                    "part2": { "answer": null, "status": "Input error", "elapsed": 0.0, "memory": null },
+                    "parse": 0.0,
+                    "parse_memory": null,
+                    "elapsed": 0.0,
+                    "error": "line 1, column 1: expected a number",
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn can_write_csv() {
+        assert_eq!(
+            render(write_csv),
+            "year,day,input,part1,part1_status,part2,part2_status,parse_seconds,part1_seconds,part2_seconds,elapsed_seconds,\
+             parse_peak_bytes,parse_allocations,part1_peak_bytes,part1_allocations,part2_peak_bytes,part2_allocations\n\
+             2023,1,,142,Done,\"a,b\",Regressed,0.250000,0.125000,0.500000,0.875000,512,1,1536,12,3145728,4000\n\
+             2023,2,\"edge, case.txt\",,Input error,,Input error,0.000000,0.000000,0.000000,0.000000,,,,,,\n"
+        );
+    }
+
+    #[test]
+    fn can_write_markdown() {
+        assert_eq!(
+            render(write_markdown),
+            "| Day | Input | Part 1 | Part 2 | Parse | Part 1 Time | Part 2 Time | Parse Memory | Part 1 Memory | Part 2 Memory |\n\
+             |----:|:------|-------:|-------:|------:|------------:|------------:|-------------:|--------------:|--------------:|\n\
+             | 1 |  | ✓ `142` | ✗ `a,b` | 0.250000s | 0.125000s | 0.500000s | 512 B, 1 allocs | 1.5 KiB, 12 allocs | 3.0 MiB, 4000 allocs |\n\
+             | 2 | edge, case.txt | Input error | Input error | 0.000000s | 0.000000s | 0.000000s |  |  |  |\n"
+        );
+    }
+}
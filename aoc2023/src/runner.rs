@@ -0,0 +1,759 @@
+use crate::cli::{Part, Scheduling};
+use crate::inputs;
+use crate::samples::Sample;
+use anyhow::{Context, Result};
+use aoc2023::memory::{self, Usage};
+use aoc2023::{Answer, Run, Timed};
+use std::io::Write;
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+/// How long days run outside of `run` (which has `--timeout`) get.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Done,
+    Pending,
+    Future,
+    Skipped,
+    Regressed,
+    InputError,
+    Panicked,
+    TimedOut,
+}
+
+impl Status {
+    fn color(&self) -> Color {
+        match self {
+            Status::Done => Color::Green,
+            Status::Pending => Color::Yellow,
+            Status::Future => Color::White,
+            Status::Skipped => Color::White,
+            Status::Regressed => Color::Red,
+            Status::InputError => Color::Red,
+            Status::Panicked => Color::Red,
+            Status::TimedOut => Color::Red,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Done => "Done",
+            Status::Pending => "Pending",
+            Status::Future => "Future",
+            Status::Skipped => "Skipped",
+            Status::Regressed => "Regressed",
+            Status::InputError => "Input error",
+            Status::Panicked => "Panicked",
+            Status::TimedOut => "Timed out",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Status::Done => "✓",
+            Status::Pending => "…",
+            Status::Future => " ",
+            Status::Skipped => "-",
+            Status::Regressed => "✗",
+            Status::InputError => "!",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed out",
+        }
+    }
+}
+
+pub struct PartResult {
+    pub answer: Option<Answer>,
+    pub status: Status,
+    pub elapsed: f64,
+    /// How much memory it used, if allocations were being counted.
+    pub memory: Option<Usage>,
+}
+
+impl PartResult {
+    fn new(answer: Option<Answer>, has_input: bool, elapsed: f64, memory: Option<Usage>) -> Self {
+        let status = match (has_input, answer.is_some()) {
+            (false, _) => Status::Future,
+            (true, true) => Status::Done,
+            (true, false) => Status::Pending,
+        };
+        PartResult {
+            answer,
+            status,
+            elapsed,
+            memory,
+        }
+    }
+
+    fn failed(status: Status) -> Self {
+        PartResult {
+            answer: None,
+            status,
+            elapsed: 0.0,
+            memory: None,
+        }
+    }
+
+    fn skipped() -> Self {
+        PartResult {
+            answer: None,
+            status: Status::Skipped,
+            elapsed: 0.0,
+            memory: None,
+        }
+    }
+
+    fn display(&self) -> String {
+        self.answer
+            .as_ref()
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| self.status.symbol().to_string())
+    }
+}
+
+pub struct DayResult {
+    pub year: u32,
+    pub day: u32,
+    /// Where the input came from, if it wasn't `inputs/YYYY/dayNN.txt`.
+    pub input: Option<String>,
+    pub part1: PartResult,
+    pub part2: PartResult,
+    pub parse: f64,
+    pub parse_memory: Option<Usage>,
+    /// Why the day couldn't be run, if it couldn't: the input error, the
+    /// panic message or how long it was given before timing out.
+    pub error: Option<String>,
+}
+
+impl DayResult {
+    /// The total time spent parsing and solving.
+    pub fn elapsed(&self) -> f64 {
+        self.parse + self.part1.elapsed + self.part2.elapsed
+    }
+
+    /// Describes what went wrong, if the day couldn't be run.
+    pub fn failure(&self) -> Option<String> {
+        let error = self.error.as_ref()?;
+        let status = [&self.part1, &self.part2]
+            .iter()
+            .map(|p| p.status)
+            .find(|s| *s != Status::Skipped)?;
+        let name = match &self.input {
+            Some(input) => format!("{} day {} ({input})", self.year, self.day),
+            None => format!("{} day {}", self.year, self.day),
+        };
+        Some(match status {
+            Status::Panicked => format!("{name} panicked: {error}"),
+            Status::TimedOut => format!("{name} timed out: {error}"),
+            _ => format!("{name} has an input error at {error}"),
+        })
+    }
+}
+
+fn part_result(part: Option<Timed<Option<Answer>>>, has_input: bool) -> PartResult {
+    match part {
+        Some(timed) => PartResult::new(
+            timed.value,
+            has_input,
+            timed.elapsed.as_secs_f64(),
+            (has_input && memory::is_counting()).then_some(timed.memory),
+        ),
+        None => PartResult::skipped(),
+    }
+}
+
+/// The message a panic was started with, if it was a string.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs the puzzle on its own thread, so that a panic or an infinite loop
+/// only takes down this day. A day that times out is left running in the
+/// background since threads can't be killed, but its result is ignored.
+fn isolated_run(
+    solution: &'static dyn aoc2023::Puzzle,
+    input: String,
+    (run1, run2): (bool, bool),
+    timeout: Duration,
+) -> Result<Run, (Status, String)> {
+    let (sender, receiver) = mpsc::channel();
+    let spawned = std::thread::Builder::new().spawn(move || {
+        // solutions are stateless, so there's nothing a panic could leave broken
+        let run = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            solution.run(&input, run1, run2)
+        }));
+        // nobody's listening any more if we timed out
+        let _ = sender.send(run);
+    });
+    if let Err(e) = spawned {
+        return Err((Status::Panicked, format!("couldn't start a thread: {e}")));
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok(run))) => Ok(run),
+        Ok(Ok(Err(e))) => Err((Status::InputError, e.to_string())),
+        Ok(Err(payload)) => Err((Status::Panicked, panic_message(payload.as_ref()))),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            Err((Status::TimedOut, format!("still running after {timeout:?}")))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err((Status::Panicked, "stopped without a result".to_string()))
+        }
+    }
+}
+
+/// Runs a single day against `inputs/YYYY/dayNN.txt`, giving up on it after
+/// `timeout`.
+pub fn run_day(year: u32, day: u32, part: Option<Part>, timeout: Duration) -> DayResult {
+    let input = std::fs::read_to_string(inputs::input_path(year, day)).ok();
+    run_input(year, day, part, input, timeout)
+}
+
+/// Runs a single day against the given input, if there is one, giving up on
+/// it after `timeout`.
+pub fn run_input(
+    year: u32,
+    day: u32,
+    part: Option<Part>,
+    input: Option<String>,
+    timeout: Duration,
+) -> DayResult {
+    let has_input = input.is_some();
+
+    let (run1, run2) = (part != Some(Part::Two), part != Some(Part::One));
+    let run = match (aoc2023::solution(year, day), input) {
+        (Some(solution), Some(input)) => {
+            match isolated_run(solution, input, (run1, run2), timeout) {
+                Ok(run) => run,
+                Err((status, error)) => {
+                    let part = |run| match run {
+                        true => PartResult::failed(status),
+                        false => PartResult::skipped(),
+                    };
+                    return DayResult {
+                        year,
+                        day,
+                        input: None,
+                        part1: part(run1),
+                        part2: part(run2),
+                        parse: 0.0,
+                        parse_memory: None,
+                        error: Some(error),
+                    };
+                }
+            }
+        }
+        // either there's no input yet, or we haven't started the day
+        _ => Run {
+            parse: Duration::ZERO,
+            parse_memory: Usage::default(),
+            part1: run1.then_some(Timed {
+                value: None,
+                elapsed: Duration::ZERO,
+                memory: Usage::default(),
+            }),
+            part2: run2.then_some(Timed {
+                value: None,
+                elapsed: Duration::ZERO,
+                memory: Usage::default(),
+            }),
+        },
+    };
+
+    DayResult {
+        year,
+        day,
+        input: None,
+        part1: part_result(run.part1, has_input),
+        part2: part_result(run.part2, has_input),
+        parse: run.parse.as_secs_f64(),
+        parse_memory: (has_input && memory::is_counting()).then_some(run.parse_memory),
+        error: None,
+    }
+}
+
+/// Runs `f` without printing panics as they happen, since they're reported
+/// in the results instead.
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let value = f();
+    std::panic::set_hook(hook);
+    value
+}
+
+/// Sizes the thread pool that days which parallelise internally run on. Has
+/// to happen before anything else uses it.
+pub fn use_threads(threads: Option<u16>) -> Result<()> {
+    let Some(threads) = threads else {
+        return Ok(());
+    };
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(usize::from(threads))
+        .build_global()
+        .context("Failed to set up the thread pool")
+}
+
+/// Maps over the days in parallel, or one at a time if they're being run
+/// sequentially.
+///
+/// Parallel days are handed out to plain threads rather than rayon's pool:
+/// each day waits on a thread of its own (see `isolated_run`), which may use
+/// the pool itself, so a pool thread waiting on it could starve it.
+fn map_days<T: Send, R: Send>(
+    days: Vec<T>,
+    scheduling: Scheduling,
+    f: impl Fn(T) -> R + Sync + Send,
+) -> Vec<R> {
+    if scheduling.sequential {
+        return days.into_iter().map(f).collect();
+    }
+    let workers = match scheduling.threads {
+        Some(threads) => usize::from(threads),
+        None => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    }
+    .min(days.len());
+    let queue = Mutex::new(days.into_iter().enumerate());
+    let mut results: Vec<(usize, R)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let Some((i, day)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        results.push((i, f(day)));
+                    }
+                    results
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("days are run in isolation"))
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Runs the given days for each of the given years.
+pub fn run_days(
+    years: &[u32],
+    days: &[u32],
+    part: Option<Part>,
+    timeout: Duration,
+    scheduling: Scheduling,
+) -> Vec<DayResult> {
+    let days = years
+        .iter()
+        .flat_map(|&year| days.iter().map(move |&day| (year, day)))
+        .collect();
+    quietly(|| {
+        map_days(days, scheduling, |(year, day)| {
+            run_day(year, day, part, timeout)
+        })
+    })
+}
+
+/// Runs a single day against one of its samples, giving up on it after
+/// `timeout`.
+pub fn run_sample(year: u32, day: u32, sample: &Sample, timeout: Duration) -> DayResult {
+    let input = Some(sample.input.clone());
+    let mut result = quietly(|| run_input(year, day, Some(sample.part), input, timeout));
+    result.input = Some("sample".to_string());
+    result
+}
+
+/// Runs each day against each of the named inputs given for it.
+pub fn run_named_inputs(
+    inputs: Vec<(u32, u32, String, String)>,
+    part: Option<Part>,
+    timeout: Duration,
+    scheduling: Scheduling,
+) -> Vec<DayResult> {
+    quietly(|| {
+        map_days(inputs, scheduling, |(year, day, name, input)| {
+            let mut result = run_input(year, day, part, Some(input), timeout);
+            result.input = Some(name);
+            result
+        })
+    })
+}
+
+fn write_elapsed(stdout: &mut StandardStream, elapsed: f64) -> Result<()> {
+    if elapsed > 0.0 {
+        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+        write!(stdout, "{:>11}", format!("{elapsed:.6}s"))?;
+    } else {
+        write!(stdout, "{:11}", "")?;
+    }
+    stdout.reset()?;
+    Ok(())
+}
+
+/// A number of bytes in the largest unit that keeps it at least 1.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// The peak usage and allocation count, or nothing if they weren't counted.
+pub fn format_memory(memory: Option<Usage>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "{}, {} allocs",
+            format_bytes(memory.peak),
+            memory.allocations
+        ),
+        None => String::new(),
+    }
+}
+
+pub fn print_table(results: &[DayResult]) -> Result<()> {
+    let (p1_width, p2_width) = results.iter().fold((0, 0), |(p1_width, p2_width), r| {
+        (
+            p1_width.max(r.part1.display().len()),
+            p2_width.max(r.part2.display().len()),
+        )
+    });
+    let p1_width = p1_width.max("Part 1".len());
+    let p2_width = p2_width.max("Part 2".len());
+    // inputs only get a column when they aren't the usual ones
+    let input_width = results
+        .iter()
+        .filter_map(|r| r.input.as_ref())
+        .map(|i| i.chars().count().max("Input".len()))
+        .max();
+    let input_column = |fill: &str, sep: &str| match input_width {
+        Some(width) => format!("{}{sep}", fill.repeat(width + 2)),
+        None => String::new(),
+    };
+    // as do memory columns, when allocations were counted
+    const MEMORY_HEADINGS: [&str; 3] = ["Parse Memory", "Part 1 Memory", "Part 2 Memory"];
+    let memory_width = results
+        .iter()
+        .filter(|r| r.parse_memory.is_some())
+        .flat_map(|r| [r.parse_memory, r.part1.memory, r.part2.memory])
+        .map(|m| format_memory(m).len().max(MEMORY_HEADINGS[2].len()))
+        .max();
+    let memory_columns = |fill: &str, sep: &str| match memory_width {
+        Some(width) => format!("{sep}{}", fill.repeat(width + 2)).repeat(3),
+        None => String::new(),
+    };
+
+    println!(
+        "╒═════╤{}═{p1:═<width1$}═╤═{p2:═<width2$}═╤═════════════╤═════════════╤═════════════{}╕",
+        input_column("═", "╤"),
+        memory_columns("═", "╤"),
+        p1 = "",
+        p2 = "",
+        width1 = p1_width,
+        width2 = p2_width
+    );
+    let input_heading = match input_width {
+        Some(width) => format!(" {:<width$} │", "Input"),
+        None => String::new(),
+    };
+    let memory_headings = match memory_width {
+        Some(width) => MEMORY_HEADINGS
+            .iter()
+            .map(|heading| format!(" {heading:>width$} │"))
+            .collect(),
+        None => String::new(),
+    };
+    println!(
+        "│ Day │{input_heading} {p1: <width1$}Part 1 │ {p2: <width2$}Part 2 │       Parse │ Part 1 Time │ Part 2 Time │{memory_headings}",
+        p1 = "",
+        p2 = "",
+        width1 = (p1_width - "Part 1".len()),
+        width2 = (p2_width - "Part 2".len())
+    );
+    println!(
+        "├─────┼{}─{p1:─<width1$}─┼─{p2:─<width2$}─┼─────────────┼─────────────┼─────────────{}┤",
+        input_column("─", "┼"),
+        memory_columns("─", "┼"),
+        p1 = "",
+        p2 = "",
+        width1 = p1_width,
+        width2 = p2_width
+    );
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    for r in results.iter() {
+        write!(stdout, "│ {:>3} │ ", r.day)?;
+        if let Some(width) = input_width {
+            write!(stdout, "{:<width$} │ ", r.input.as_deref().unwrap_or(""))?;
+        }
+        stdout.set_color(ColorSpec::new().set_fg(Some(r.part1.status.color())))?;
+        write!(stdout, "{:>width1$}", r.part1.display(), width1 = p1_width)?;
+        stdout.reset()?;
+        write!(stdout, " │ ")?;
+        stdout.set_color(ColorSpec::new().set_fg(Some(r.part2.status.color())))?;
+        write!(stdout, "{:>width2$}", r.part2.display(), width2 = p2_width)?;
+        stdout.reset()?;
+        for elapsed in [r.parse, r.part1.elapsed, r.part2.elapsed] {
+            write!(stdout, " │ ")?;
+            write_elapsed(&mut stdout, elapsed)?;
+        }
+        if let Some(width) = memory_width {
+            for memory in [r.parse_memory, r.part1.memory, r.part2.memory] {
+                write!(stdout, " │ ")?;
+                stdout.set_color(ColorSpec::new().set_fg(Some(Color::Magenta)))?;
+                write!(stdout, "{:>width$}", format_memory(memory))?;
+                stdout.reset()?;
+            }
+        }
+        writeln!(stdout, " │")?;
+    }
+    println!(
+        "╘═════╧{}═{p1:═<width1$}═╧═{p2:═<width2$}═╧═════════════╧═════════════╧═════════════{}╛",
+        input_column("═", "╧"),
+        memory_columns("═", "╧"),
+        p1 = "",
+        p2 = "",
+        width1 = p1_width,
+        width2 = p2_width
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc2023::{ParseError, Solution};
+
+    /// A day whose input says how to misbehave.
+    struct Misbehaving;
+
+    impl Solution for Misbehaving {
+        type Input<'i> = &'i str;
+
+        fn year(&self) -> u32 {
+            2023
+        }
+
+        fn day(&self) -> u32 {
+            99
+        }
+
+        fn title(&self) -> &'static str {
+            "Misbehaving"
+        }
+
+        fn parse<'i>(&self, input: &'i str) -> Result<&'i str, ParseError> {
+            match input {
+                "bad" => Err(ParseError::at(input, input, "bad input")),
+                _ => Ok(input),
+            }
+        }
+
+        fn part1(&self, input: &&str) -> Option<Answer> {
+            match *input {
+                "panic" => panic!("no start found"),
+                "hang" => loop {
+                    std::thread::sleep(Duration::from_millis(10));
+                },
+                _ => Some(1usize.into()),
+            }
+        }
+
+        fn part2(&self, _input: &&str) -> Option<Answer> {
+            None
+        }
+    }
+
+    fn misbehave(input: &str) -> Result<Run, (Status, String)> {
+        isolated_run(
+            &Misbehaving,
+            input.to_string(),
+            (true, true),
+            Duration::from_millis(200),
+        )
+    }
+
+    #[test]
+    fn isolates_panics_and_timeouts() {
+        let run = misbehave("fine").unwrap();
+        assert_eq!(run.part1.unwrap().value, Some(1usize.into()));
+
+        let (status, error) = misbehave("bad").unwrap_err();
+        assert_eq!(status, Status::InputError);
+        assert!(error.starts_with("line 1, column 1: bad input"));
+
+        assert_eq!(
+            misbehave("panic").unwrap_err(),
+            (Status::Panicked, "no start found".to_string())
+        );
+
+        let (status, error) = misbehave("hang").unwrap_err();
+        assert_eq!(status, Status::TimedOut);
+        assert_eq!(error, "still running after 200ms");
+    }
+
+    #[test]
+    fn describes_failures() {
+        let result = |status, error: Option<&str>| DayResult {
+            year: 2023,
+            day: 10,
+            input: None,
+            part1: PartResult::failed(status),
+            part2: PartResult::skipped(),
+            parse: 0.0,
+            parse_memory: None,
+            error: error.map(|e| e.to_string()),
+        };
+        assert_eq!(
+            result(Status::Panicked, Some("no start found")).failure(),
+            Some("2023 day 10 panicked: no start found".to_string())
+        );
+        assert_eq!(
+            result(Status::TimedOut, Some("still running after 1s")).failure(),
+            Some("2023 day 10 timed out: still running after 1s".to_string())
+        );
+        assert_eq!(result(Status::Done, None).failure(), None);
+
+        let mut result = result(Status::InputError, Some("line 1, column 1: oops"));
+        result.input = Some("stdin".to_string());
+        assert_eq!(
+            result.failure(),
+            Some("2023 day 10 (stdin) has an input error at line 1, column 1: oops".to_string())
+        );
+    }
+
+    /// A day that parallelises internally.
+    struct Parallel;
+
+    impl Solution for Parallel {
+        type Input<'i> = &'i str;
+
+        fn year(&self) -> u32 {
+            2023
+        }
+
+        fn day(&self) -> u32 {
+            98
+        }
+
+        fn title(&self) -> &'static str {
+            "Parallel"
+        }
+
+        fn parse<'i>(&self, input: &'i str) -> Result<&'i str, ParseError> {
+            Ok(input)
+        }
+
+        fn part1(&self, _input: &&str) -> Option<Answer> {
+            use rayon::prelude::*;
+            Some((1..=100usize).into_par_iter().sum::<usize>().into())
+        }
+
+        fn part2(&self, _input: &&str) -> Option<Answer> {
+            None
+        }
+    }
+
+    /// Shrinks the global pool to a single thread, once for every test that
+    /// needs it (nothing else in these tests uses the pool).
+    fn single_threaded_pool() {
+        static POOL: std::sync::Once = std::sync::Once::new();
+        POOL.call_once(|| use_threads(Some(1)).unwrap());
+    }
+
+    #[test]
+    fn parallel_days_dont_starve_the_pool() {
+        single_threaded_pool();
+        let parallel = Scheduling {
+            sequential: false,
+            threads: None,
+        };
+        let started = std::time::Instant::now();
+        let runs = map_days(vec!["a", "b", "c"], parallel, |input| {
+            isolated_run(
+                &Parallel,
+                input.to_string(),
+                (true, false),
+                Duration::from_secs(5),
+            )
+        });
+        for run in runs {
+            assert_eq!(run.unwrap().part1.unwrap().value, Some(5050usize.into()));
+        }
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn runs_several_parallel_days_on_one_thread() {
+        single_threaded_pool();
+        let one_thread = Scheduling {
+            sequential: false,
+            threads: Some(1),
+        };
+        let runs = map_days((0..4).collect(), one_thread, |i: usize| {
+            isolated_run(
+                &Parallel,
+                i.to_string(),
+                (true, false),
+                Duration::from_secs(5),
+            )
+        });
+        assert_eq!(runs.len(), 4);
+        for run in runs {
+            assert_eq!(run.unwrap().part1.unwrap().value, Some(5050usize.into()));
+        }
+    }
+
+    #[test]
+    fn formats_memory_usage() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+        assert_eq!(format_bytes(5 << 40), "5.0 TiB");
+        assert_eq!(format_bytes(2048 << 40), "2048.0 TiB");
+        let usage = Usage {
+            peak: 1 << 30,
+            allocations: 42,
+        };
+        assert_eq!(format_memory(Some(usage)), "1.0 GiB, 42 allocs");
+        assert_eq!(format_memory(None), "");
+    }
+
+    #[test]
+    fn sequential_days_run_one_at_a_time() {
+        let order = std::sync::Mutex::new(Vec::new());
+        let sequential = Scheduling {
+            sequential: true,
+            threads: None,
+        };
+        let results = map_days((0..20).collect(), sequential, |i| {
+            order.lock().unwrap().push(i);
+            i * 2
+        });
+        assert_eq!(results, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(order.into_inner().unwrap(), (0..20).collect::<Vec<_>>());
+
+        let results = map_days((0..20).collect(), Scheduling::default(), |i| i * 2);
+        assert_eq!(results, (0..20).map(|i| i * 2).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,268 @@
+use crate::cli::{Headless, Part};
+use crate::puzzle::{self, strip_tags};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// An example from a puzzle's description, along with the answer it should
+/// give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Samples are kept per year, in `samples/YYYY/dayNN.toml`. They're extracted
+/// from the puzzle description, so they can be edited by hand if the
+/// extraction picked the wrong example.
+pub fn sample_path(year: u32, day: u32) -> PathBuf {
+    Path::new("samples")
+        .join(year.to_string())
+        .join(format!("day{:02}.toml", day))
+}
+
+/// Pulls the first example and the last emphasised answer out of each part's
+/// description. Part 2 often reuses part 1's example, so it does too if it
+/// doesn't have one of its own.
+pub fn extract_samples(html: &str) -> Vec<Sample> {
+    let article =
+        Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).expect("valid regex");
+    let example = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").expect("valid regex");
+    let answer = Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>")
+        .expect("valid regex");
+
+    let mut samples: Vec<Sample> = Vec::new();
+    let mut previous_input = None;
+    for (article, part) in article.captures_iter(html).zip([Part::One, Part::Two]) {
+        let article = &article[1];
+        let input = example
+            .captures(article)
+            .map(|c| strip_tags(&c[1]))
+            .or(previous_input.take());
+        // examples can have emphasis of their own, so don't look for answers in them
+        let prose = example.replace_all(article, "");
+        let expected = answer
+            .captures_iter(&prose)
+            .last()
+            .and_then(|c| c.get(1).or(c.get(2)))
+            .map(|m| strip_tags(m.as_str()));
+        if let (Some(input), Some(expected)) = (&input, expected) {
+            samples.push(Sample {
+                part,
+                input: input.clone(),
+                answer: expected,
+            });
+        }
+        previous_input = input;
+    }
+    samples
+}
+
+pub fn to_toml(samples: &[Sample]) -> String {
+    samples
+        .iter()
+        .map(|sample| {
+            format!(
+                "[[sample]]\npart = {}\ninput = {}\nanswer = {}\n",
+                sample.part,
+                toml::Value::String(sample.input.clone()),
+                toml::Value::String(sample.answer.clone())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn parse(contents: &str) -> Result<Vec<Sample>> {
+    let table = contents.parse::<toml::Table>()?;
+    let Some(samples) = table.get("sample") else {
+        return Ok(Vec::new());
+    };
+    let samples = samples
+        .as_array()
+        .context("'sample' must be an array of tables")?;
+    samples
+        .iter()
+        .map(|sample| {
+            let field = |name: &str| {
+                sample
+                    .get(name)
+                    .with_context(|| format!("Missing '{name}' in sample"))
+            };
+            let part = match field("part")?.as_integer() {
+                Some(1) => Part::One,
+                Some(2) => Part::Two,
+                _ => bail!("'part' must be 1 or 2"),
+            };
+            let input = field("input")?
+                .as_str()
+                .context("'input' must be a string")?;
+            let answer = field("answer")?
+                .as_str()
+                .context("'answer' must be a string")?;
+            Ok(Sample {
+                part,
+                input: input.to_string(),
+                answer: answer.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn read_samples(path: &Path) -> Result<Vec<Sample>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read samples from {}", path.display()))?;
+    parse(&contents).with_context(|| format!("Invalid samples in {}", path.display()))
+}
+
+fn write_samples(path: &Path, samples: &[Sample]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create samples directory: {}", dir.display()))?;
+    }
+    std::fs::write(path, to_toml(samples))
+        .with_context(|| format!("Failed to write samples to {}", path.display()))
+}
+
+/// Loads the samples for a day, fetching them from the puzzle description if
+/// they haven't been yet. Returns `None` if the description isn't available.
+pub fn load_samples(year: u32, day: u32, headless: Headless) -> Result<Option<Vec<Sample>>> {
+    let path = sample_path(year, day);
+    if path.exists() {
+        return read_samples(&path).map(Some);
+    }
+    let Some(html) = puzzle::load_puzzle(year, day, false, headless)? else {
+        return Ok(None);
+    };
+    let samples = extract_samples(&html);
+    if samples.is_empty() {
+        bail!("Couldn't find any samples for {year} day {day}");
+    }
+    write_samples(&path, &samples)?;
+    Ok(Some(samples))
+}
+
+/// Adds the extracted samples for any parts that don't have one yet, leaving
+/// the ones that do alone since they may have been fixed by hand. Returns
+/// `None` if there's nothing new.
+fn merge_samples(existing: &[Sample], extracted: Vec<Sample>) -> Option<Vec<Sample>> {
+    let new: Vec<Sample> = extracted
+        .into_iter()
+        .filter(|sample| existing.iter().all(|s| s.part != sample.part))
+        .collect();
+    if new.is_empty() {
+        return None;
+    }
+    let mut samples = existing.to_vec();
+    samples.extend(new);
+    samples.sort_by_key(|s| s.part);
+    Some(samples)
+}
+
+/// Re-extracts a day's samples from a freshly downloaded description, so that
+/// samples saved before part 2 unlocked pick up part 2's.
+pub fn refresh_samples(year: u32, day: u32, html: &str) -> Result<()> {
+    let path = sample_path(year, day);
+    let existing = match path.exists() {
+        true => read_samples(&path)?,
+        false => Vec::new(),
+    };
+    if let Some(samples) = merge_samples(&existing, extract_samples(html)) {
+        write_samples(&path, &samples)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUZZLE: &str = include_str!("../fixtures/puzzle-2023-day01.html");
+
+    #[test]
+    fn can_extract_samples() {
+        let samples = extract_samples(PUZZLE);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].part, Part::One);
+        assert_eq!(
+            samples[0].input,
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        );
+        assert_eq!(samples[0].answer, "142");
+        assert_eq!(samples[1].part, Part::Two);
+        assert!(samples[1].input.starts_with("two1nine\neightwothree\n"));
+        assert_eq!(samples[1].answer, "281");
+    }
+
+    #[test]
+    fn part2_reuses_part1_example() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 9: Example ---</h2>
+<pre><code>0 3 &lt;6&gt;
+<em>1</em> 3 6
+</code></pre>
+<p>The sum is <em><code>114</code></em>.</p></article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Going backwards gives <code><em>2</em></code>.</p></article>
+</main>"#;
+        let samples = extract_samples(html);
+        assert_eq!(
+            samples,
+            vec![
+                Sample {
+                    part: Part::One,
+                    input: "0 3 <6>\n1 3 6\n".to_string(),
+                    answer: "114".to_string(),
+                },
+                Sample {
+                    part: Part::Two,
+                    input: "0 3 <6>\n1 3 6\n".to_string(),
+                    answer: "2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn refreshing_adds_new_parts() {
+        let extracted = extract_samples(PUZZLE);
+        let mut edited = extracted[0].clone();
+        edited.answer = "fixed by hand".to_string();
+
+        let merged = merge_samples(&[edited.clone()], extracted.clone()).unwrap();
+        assert_eq!(merged, vec![edited, extracted[1].clone()]);
+        assert_eq!(merge_samples(&merged, extracted.clone()), None);
+        assert_eq!(merge_samples(&[], extracted.clone()), Some(extracted));
+    }
+
+    #[test]
+    fn samples_round_trip() {
+        let samples = extract_samples(PUZZLE);
+        assert_eq!(parse(&to_toml(&samples)).unwrap(), samples);
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse("[[sample]]\npart = 3\ninput = \"\"\nanswer = \"\"\n").is_err());
+    }
+
+    #[test]
+    fn samples_solve_day01() {
+        let puzzle = aoc2023::solution(2023, 1).unwrap();
+        for sample in extract_samples(PUZZLE) {
+            let run = puzzle
+                .run(
+                    &sample.input,
+                    sample.part == Part::One,
+                    sample.part == Part::Two,
+                )
+                .unwrap();
+            let answer = match sample.part {
+                Part::One => run.part1,
+                Part::Two => run.part2,
+            };
+            assert_eq!(
+                answer.and_then(|a| a.value).map(|a| a.to_string()),
+                Some(sample.answer)
+            );
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use crate::cli::Part;
+use crate::samples::Sample;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Writes `input` as a raw string literal, with enough `#`s to hold it.
+fn raw_string(input: &str) -> String {
+    let mut hashes = "#".to_string();
+    while input.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{input}\"{hashes}")
+}
+
+/// Numbers are compared as numbers in the sample tests, anything else as a
+/// string.
+fn expected(answer: &str) -> String {
+    if answer.parse::<u64>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}")
+    }
+}
+
+/// 2023's days came first and live at the top level, in `src/dayNN` as
+/// `dayNN::DayNN`. Any other year gets a module of its own, in
+/// `src/yYYYY/dayNN` as `yYYYY::dayNN::YYYYYDayNN`, so days from different
+/// years can't be mixed up.
+const FIRST_YEAR: u32 = 2023;
+
+/// Where a day's module lives and what it's called.
+#[derive(Debug, PartialEq, Eq)]
+struct DayModule {
+    /// The year's module, for years other than the first.
+    year_module: Option<String>,
+    /// The day's module, within the year's module if there is one.
+    module: String,
+    type_name: String,
+}
+
+impl DayModule {
+    fn new(year: u32, day: u32) -> Self {
+        match year {
+            FIRST_YEAR => DayModule {
+                year_module: None,
+                module: format!("day{day:02}"),
+                type_name: format!("Day{day:02}"),
+            },
+            _ => DayModule {
+                year_module: Some(format!("y{year}")),
+                module: format!("day{day:02}"),
+                type_name: format!("Y{year}Day{day:02}"),
+            },
+        }
+    }
+
+    /// The module's directory under `src/`.
+    fn dir(&self) -> String {
+        match &self.year_module {
+            Some(year) => format!("src/{year}/{}", self.module),
+            None => format!("src/{}", self.module),
+        }
+    }
+
+    /// The type's path from the crate root.
+    fn path(&self) -> String {
+        match &self.year_module {
+            Some(year) => format!("{year}::{}::{}", self.module, self.type_name),
+            None => format!("{}::{}", self.module, self.type_name),
+        }
+    }
+}
+
+/// The directory holding a day's source, wherever its year puts it.
+pub fn day_dir(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(DayModule::new(year, day).dir())
+}
+
+/// The skeleton for a new day. Both parts return `None` until they're filled
+/// in, so the runner shows them as pending; the sample
+/// tests are filled in from the puzzle's examples if we have them, but are
+/// ignored until then so that `cargo test` still passes.
+pub fn day_module(year: u32, day: u32, title: &str, samples: &[Sample]) -> String {
+    let sample = |part: Part| samples.iter().find(|s| s.part == part);
+    let part1 = sample(Part::One);
+    let part2 = sample(Part::Two);
+
+    let mut constants = format!(
+        "    const SAMPLE: &str = {};\n",
+        raw_string(part1.or(part2).map(|s| s.input.as_str()).unwrap_or(""))
+    );
+    let part2_sample = match (part1, part2) {
+        (Some(p1), Some(p2)) if p1.input != p2.input => {
+            constants.push_str(&format!(
+                "    const SAMPLE2: &str = {};\n",
+                raw_string(&p2.input)
+            ));
+            "SAMPLE2"
+        }
+        _ => "SAMPLE",
+    };
+    let type_name = DayModule::new(year, day).type_name;
+    let answer = |sample: Option<&Sample>| {
+        sample
+            .map(|s| expected(&s.answer))
+            .unwrap_or_else(|| "0".to_string())
+    };
+
+    format!(
+        r#"use crate::{{Answer, ParseError, Solution}};
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {{
+    Ok(input.lines().collect())
+}}
+
+// return `Some` answer once it's solved, and un-ignore its test
+pub fn part1(_lines: &[&str]) -> Option<usize> {{
+    None
+}}
+
+pub fn part2(_lines: &[&str]) -> Option<usize> {{
+    None
+}}
+
+pub struct {type_name};
+
+impl Solution for {type_name} {{
+    type Input<'i> = Vec<&'i str>;
+
+    fn year(&self) -> u32 {{
+        {year}
+    }}
+
+    fn day(&self) -> u32 {{
+        {day}
+    }}
+
+    fn title(&self) -> &'static str {{
+        {title:?}
+    }}
+
+    fn parse<'i>(&self, input: &'i str) -> Result<Vec<&'i str>, ParseError> {{
+        parse(input)
+    }}
+
+    fn part1(&self, lines: &Self::Input<'_>) -> Option<Answer> {{
+        part1(lines).map(Answer::from)
+    }}
+
+    fn part2(&self, lines: &Self::Input<'_>) -> Option<Answer> {{
+        part2(lines).map(Answer::from)
+    }}
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+{constants}
+    #[test]
+    #[ignore = "part 1 isn't solved yet"]
+    fn day{day:02}_sample_part1() {{
+        assert_eq!(part1(&parse(SAMPLE).unwrap()), Some({answer1}));
+    }}
+
+    #[test]
+    #[ignore = "part 2 isn't solved yet"]
+    fn day{day:02}_sample_part2() {{
+        assert_eq!(part2(&parse({part2_sample}).unwrap()), Some({answer2}));
+    }}
+}}
+"#,
+        answer1 = answer(part1),
+        answer2 = answer(part2),
+    )
+}
+
+/// Inserts `line` among the existing lines that start with `prefix`, keeping
+/// them sorted. Returns `None` if it's already there.
+fn insert_sorted(contents: &str, prefix: &str, line: &str) -> Option<String> {
+    insert_sorted_by(contents, prefix, line, |l| l.to_string())
+}
+
+/// Like [`insert_sorted`], but sorting the lines by `key`.
+fn insert_sorted_by<K: Ord>(
+    contents: &str,
+    prefix: &str,
+    line: &str,
+    key: impl Fn(&str) -> K,
+) -> Option<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    if lines.iter().any(|l| l.trim_end() == line) {
+        return None;
+    }
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with(prefix))
+        .collect();
+    let index = matching
+        .iter()
+        .copied()
+        .find(|&i| key(lines[i]) > key(line))
+        .or(matching.last().map(|&i| i + 1))?;
+
+    let mut lines = lines;
+    lines.insert(index, line);
+    Some(lines.join("\n") + "\n")
+}
+
+/// The year and day of an entry in `SOLUTIONS`, which has to stay ordered by
+/// them.
+fn registry_key(line: &str) -> (u32, u32) {
+    let path = line.trim().trim_start_matches('&');
+    let (year, day) = match path.strip_prefix('y') {
+        Some(rest) => rest.split_once("::").unwrap_or((rest, "")),
+        None => ("2023", path),
+    };
+    let number = |s: &str| {
+        s.trim_start_matches("day")
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0)
+    };
+    (number(year), number(day))
+}
+
+/// Adds the day's module (or its year's) and its entry in `SOLUTIONS`.
+/// Returns `None` if it's already registered.
+pub fn register(lib: &str, year: u32, day: u32) -> Option<String> {
+    let module = DayModule::new(year, day);
+    let declaration = match &module.year_module {
+        Some(year) => insert_sorted(lib, "pub mod ", &format!("pub mod {year};")),
+        None => insert_sorted(lib, "pub mod day", &format!("pub mod day{day:02};")),
+    };
+    let lib = declaration.as_deref().unwrap_or(lib);
+    let entry = format!("    &{},", module.path());
+    insert_sorted_by(lib, "    &", &entry, registry_key).or(declaration)
+}
+
+/// The year a day's module is for, going by its `Solution::year`.
+fn module_year(module: &str) -> Option<u32> {
+    let (_, rest) = module.split_once("fn year(&self) -> u32 {")?;
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+/// Creates everything a new day needs under `root`, skipping anything that
+/// already exists. Returns what was done to each file.
+pub fn scaffold(
+    root: &Path,
+    year: u32,
+    day: u32,
+    title: &str,
+    samples: &[Sample],
+) -> Result<Vec<(String, bool)>> {
+    let mut changes = Vec::new();
+    let mut create = |path: &str, contents: &dyn Fn() -> String| -> Result<()> {
+        let full = root.join(path);
+        let created = !full.exists();
+        if created {
+            if let Some(dir) = full.parent() {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            std::fs::write(&full, contents())
+                .with_context(|| format!("Failed to write {}", full.display()))?;
+        }
+        changes.push((path.to_string(), created));
+        Ok(())
+    };
+    let module = DayModule::new(year, day);
+    let module_path = format!("{}/mod.rs", module.dir());
+    if let Ok(existing) = std::fs::read_to_string(root.join(&module_path)) {
+        match module_year(&existing) {
+            Some(existing) if existing != year => {
+                bail!("{module_path} already holds {existing}'s day {day}, not {year}'s")
+            }
+            _ => {}
+        }
+    }
+    create(&module_path, &|| day_module(year, day, title, samples))?;
+    // a year's first day creates its module, later ones are added to it
+    let declaration = format!("pub mod {};", module.module);
+    let year_path = module
+        .year_module
+        .as_ref()
+        .map(|year_module| format!("src/{year_module}/mod.rs"));
+    let year_exists = year_path.as_ref().is_some_and(|p| root.join(p).exists());
+    if let (Some(year_path), false) = (&year_path, year_exists) {
+        create(year_path, &|| format!("{declaration}\n"))?;
+    }
+
+    let mut update = |path: &str, change: &dyn Fn(&str) -> Option<String>| -> Result<()> {
+        let full = root.join(path);
+        let contents = std::fs::read_to_string(&full)
+            .with_context(|| format!("Failed to read {}", full.display()))?;
+        let changed = change(&contents);
+        if let Some(changed) = &changed {
+            std::fs::write(&full, changed)
+                .with_context(|| format!("Failed to write {}", full.display()))?;
+        }
+        changes.push((path.to_string(), changed.is_some()));
+        Ok(())
+    };
+    if let (Some(year_path), true) = (&year_path, year_exists) {
+        update(year_path, &|mods| {
+            insert_sorted(mods, "pub mod day", &declaration)
+        })?;
+    }
+    update("src/lib.rs", &|lib| register(lib, year, day))?;
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIB: &str = "mod answer;
+
+pub mod day01;
+pub mod day03;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn can_register_days() {
+        let lib = register(LIB, 2023, 2).unwrap();
+        assert_eq!(
+            lib,
+            "mod answer;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+"
+        );
+        assert_eq!(register(&lib, 2023, 2), None);
+
+        let lib = register(LIB, 2023, 12).unwrap();
+        assert!(lib.contains("pub mod day03;\npub mod day12;\n"));
+        assert!(lib.contains("    &day03::Day03,\n    &day12::Day12,\n];"));
+    }
+
+    #[test]
+    fn can_register_days_from_other_years() {
+        let lib = register(LIB, 2024, 1).unwrap();
+        let lib = register(&lib, 2024, 5).unwrap();
+        let lib = register(&lib, 2022, 25).unwrap();
+        let lib = register(&lib, 2023, 2).unwrap();
+        assert_eq!(
+            lib,
+            "mod answer;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod y2022;
+pub mod y2024;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &y2022::day25::Y2022Day25,
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &y2024::day01::Y2024Day01,
+    &y2024::day05::Y2024Day05,
+];
+"
+        );
+        assert_eq!(register(&lib, 2024, 5), None);
+    }
+
+    #[test]
+    fn fills_in_samples() {
+        let samples = [
+            Sample {
+                part: Part::One,
+                input: "1abc2\n\"#\n".to_string(),
+                answer: "142".to_string(),
+            },
+            Sample {
+                part: Part::Two,
+                input: "two1nine\n".to_string(),
+                answer: "abc".to_string(),
+            },
+        ];
+        let module = day_module(2023, 12, "Hot Springs", &samples);
+        assert!(module.contains("const SAMPLE: &str = r##\"1abc2\n\"#\n\"##;"));
+        assert!(module.contains("const SAMPLE2: &str = r#\"two1nine\n\"#;"));
+        assert!(module.contains("assert_eq!(part1(&parse(SAMPLE).unwrap()), Some(142));"));
+        assert!(module.contains("assert_eq!(part2(&parse(SAMPLE2).unwrap()), Some(\"abc\"));"));
+        assert!(module.contains("\"Hot Springs\""));
+        assert!(
+            module.contains("#[ignore = \"part 1 isn't solved yet\"]\n    fn day12_sample_part1")
+        );
+    }
+
+    #[test]
+    fn does_not_clobber_existing_work() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/day12")).unwrap();
+        std::fs::write(dir.path().join("src/day12/mod.rs"), "// my work").unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), LIB).unwrap();
+
+        let changes = scaffold(dir.path(), 2023, 12, "Hot Springs", &[]).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ("src/day12/mod.rs".to_string(), false),
+                ("src/lib.rs".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/day12/mod.rs")).unwrap(),
+            "// my work"
+        );
+
+        let changes = scaffold(dir.path(), 2023, 12, "Hot Springs", &[]).unwrap();
+        assert!(changes.iter().all(|(_, changed)| !changed), "{changes:?}");
+    }
+
+    #[test]
+    fn keeps_years_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), LIB).unwrap();
+        std::fs::create_dir_all(dir.path().join("src/day01")).unwrap();
+        std::fs::write(
+            dir.path().join("src/day01/mod.rs"),
+            day_module(2023, 1, "Trebuchet?!", &[]),
+        )
+        .unwrap();
+
+        let changes = scaffold(dir.path(), 2024, 1, "Historian Hysteria", &[]).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ("src/y2024/day01/mod.rs".to_string(), true),
+                ("src/y2024/mod.rs".to_string(), true),
+                ("src/lib.rs".to_string(), true),
+            ]
+        );
+        let module = std::fs::read_to_string(dir.path().join("src/y2024/day01/mod.rs")).unwrap();
+        assert!(module.contains("impl Solution for Y2024Day01 {"));
+        assert_eq!(module_year(&module), Some(2024));
+
+        let changes = scaffold(dir.path(), 2024, 2, "Red-Nosed Reports", &[]).unwrap();
+        assert_eq!(changes[1], ("src/y2024/mod.rs".to_string(), true));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/y2024/mod.rs")).unwrap(),
+            "pub mod day01;\npub mod day02;\n"
+        );
+
+        // a module that's for another year is a mistake, not existing work
+        std::fs::write(
+            dir.path().join("src/y2024/day01/mod.rs"),
+            day_module(2023, 1, "Trebuchet?!", &[]),
+        )
+        .unwrap();
+        let error = scaffold(dir.path(), 2024, 1, "Historian Hysteria", &[]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "src/y2024/day01/mod.rs already holds 2023's day 1, not 2024's"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::memory::{self, Usage};
+use crate::{Answer, ParseError};
+use std::time::{Duration, Instant};
+
+/// A single day's puzzle.
+///
+/// Each `dayNN` module exposes a unit struct implementing this trait, which is
+/// then registered in [`crate::SOLUTIONS`] so the runner can find it. The
+/// input is parsed once and then shared by both parts.
+pub trait Solution: Sync {
+    /// The parsed puzzle input, which may borrow from the raw input.
+    type Input<'i>;
+
+    /// The year of the event this puzzle is from.
+    fn year(&self) -> u32;
+
+    /// The day of the month this puzzle unlocks on (1-25).
+    fn day(&self) -> u32;
+
+    /// The puzzle's title, as shown on adventofcode.com.
+    fn title(&self) -> &'static str;
+
+    /// Parses the raw input, pointing at where it went wrong if it isn't
+    /// what the puzzle describes.
+    fn parse<'i>(&self, input: &'i str) -> Result<Self::Input<'i>, ParseError>;
+
+    /// Solves part 1, or returns `None` if it hasn't been solved yet.
+    fn part1(&self, input: &Self::Input<'_>) -> Option<Answer>;
+
+    /// Solves part 2, or returns `None` if it hasn't been solved yet.
+    fn part2(&self, input: &Self::Input<'_>) -> Option<Answer>;
+}
+
+/// How long a single stage of a solution took, what it returned, and how much
+/// memory it used (if allocations are being counted).
+#[derive(Debug, Clone)]
+pub struct Timed<T> {
+    pub value: T,
+    pub elapsed: Duration,
+    pub memory: Usage,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> Timed<T> {
+    let now = Instant::now();
+    let (value, memory) = memory::measure(f);
+    Timed {
+        value,
+        elapsed: now.elapsed(),
+        memory,
+    }
+}
+
+/// The results of running a solution, with each stage timed separately. Parts
+/// that weren't asked for are `None`.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse: Duration,
+    pub parse_memory: Usage,
+    pub part1: Option<Timed<Option<Answer>>>,
+    pub part2: Option<Timed<Option<Answer>>>,
+}
+
+/// The object-safe side of [`Solution`], which is what the registry holds.
+/// Every `Solution` is a `Puzzle`.
+pub trait Puzzle: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+
+    /// Parses the input and runs the requested parts against it.
+    fn run(&self, input: &str, part1: bool, part2: bool) -> Result<Run, ParseError>;
+}
+
+impl<S: Solution> Puzzle for S {
+    fn year(&self) -> u32 {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> u32 {
+        Solution::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn run(&self, input: &str, part1: bool, part2: bool) -> Result<Run, ParseError> {
+        let parsed = timed(|| self.parse(input));
+        let input = parsed.value?;
+        Ok(Run {
+            parse: parsed.elapsed,
+            parse_memory: parsed.memory,
+            part1: part1.then(|| timed(|| self.part1(&input))),
+            part2: part2.then(|| timed(|| self.part2(&input))),
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// The request the stand-in was sent.
+pub struct Request {
+    /// e.g. `GET /2023/day/1 HTTP/1.1`
+    pub line: String,
+    /// Header names are lowercased.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves a single request with the given status, content type and body.
+/// Returns the base URL to reach it at, and a handle that gives back the
+/// request once it's been served.
+pub fn serve_once(
+    status: &'static str,
+    content_type: &'static str,
+    body: impl Into<String>,
+) -> (String, JoinHandle<Request>) {
+    let body = body.into();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut headers = Vec::new();
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header.split_once(": ").unwrap();
+            headers.push((name.to_ascii_lowercase(), value.to_string()));
+        }
+        let mut request = Request {
+            line: line.trim_end().to_string(),
+            headers,
+            body: String::new(),
+        };
+        let length = request
+            .header("content-length")
+            .map_or(0, |length| length.parse().unwrap());
+        let mut request_body = vec![0; length];
+        reader.read_exact(&mut request_body).unwrap();
+        request.body = String::from_utf8(request_body).unwrap();
+
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        request
+    });
+    (base_url, server)
+}
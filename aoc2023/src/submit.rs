@@ -0,0 +1,256 @@
+use crate::cli::{Headless, Part};
+use crate::inputs;
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::time::Duration;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// We submitted too recently, and have to wait this long (if AoC told us)
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+    /// A response we don't know how to interpret, with its text
+    Unknown(String),
+}
+
+impl Outcome {
+    fn color(&self) -> Color {
+        match self {
+            Outcome::Correct => Color::Green,
+            Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow => Color::Red,
+            Outcome::RateLimited(_) => Color::Yellow,
+            Outcome::AlreadySolved => Color::Cyan,
+            Outcome::Unknown(_) => Color::Magenta,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Outcome::Correct => "That's the right answer!".to_string(),
+            Outcome::Wrong => "That's not the right answer.".to_string(),
+            Outcome::TooHigh => "That's not the right answer; it's too high.".to_string(),
+            Outcome::TooLow => "That's not the right answer; it's too low.".to_string(),
+            Outcome::RateLimited(Some(wait)) => format!(
+                "You gave an answer too recently; wait {} before trying again.",
+                format_wait(*wait)
+            ),
+            Outcome::RateLimited(None) => "You gave an answer too recently.".to_string(),
+            Outcome::AlreadySolved => "You've already solved this part.".to_string(),
+            Outcome::Unknown(text) => format!("Unrecognized response: {text}"),
+        }
+    }
+}
+
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+/// Pulls the message out of the page AoC sends back after submitting, with
+/// any markup stripped.
+fn response_text(body: &str) -> String {
+    let article = regex::Regex::new(r"(?s)<article>(.*?)</article>").expect("valid regex");
+    let tags = regex::Regex::new(r"<[^>]*>").expect("valid regex");
+    let text = article
+        .captures(body)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str())
+        .unwrap_or(body);
+    let text = tags.replace_all(text, "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    let re = regex::Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").expect("valid regex");
+    let captures = re.captures(text)?;
+    let minutes = captures
+        .get(1)
+        .map(|m| m.as_str().parse::<u64>())
+        .transpose()
+        .ok()?
+        .unwrap_or(0);
+    let seconds = captures.get(2)?.as_str().parse::<u64>().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+pub fn parse_response(body: &str) -> Outcome {
+    let text = response_text(body);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+fn level(part: Part) -> &'static str {
+    match part {
+        Part::One => "1",
+        Part::Two => "2",
+    }
+}
+
+pub fn submit_answer(
+    base_url: &str,
+    session: &str,
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &str,
+) -> Result<Outcome> {
+    let url = format!("{base_url}/{year}/day/{day}/answer");
+    let body = ureq::post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .send_form(&[("level", level(part)), ("answer", answer)])
+        .with_context(|| format!("Failed to submit answer for day {day}"))?
+        .into_string()
+        .with_context(|| format!("Failed to read submission response for day {day}"))?;
+    Ok(parse_response(&body))
+}
+
+pub fn submit(
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &str,
+    headless: Headless,
+) -> Result<Outcome> {
+    if headless.offline {
+        bail!("Can't submit answers while offline");
+    }
+    let session =
+        inputs::get_session(headless).with_context(|| "Failed to get AOC session auth")?;
+    let outcome = submit_answer(&inputs::base_url(), &session, year, day, part, answer)?;
+    report(day, part, answer, &outcome)?;
+    Ok(outcome)
+}
+
+fn report(day: u32, part: Part, answer: &str, outcome: &Outcome) -> Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    write!(stdout, "Day {day} part {}: {answer} → ", level(part))?;
+    stdout.set_color(
+        ColorSpec::new()
+            .set_fg(Some(outcome.color()))
+            .set_bold(true),
+    )?;
+    write!(stdout, "{}", outcome.message())?;
+    stdout.reset()?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::stand_in;
+
+    fn page(message: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 1 - Advent of Code 2023</title></head>
+<body>
+<main>
+<article><p>{message}</p></article>
+</main>
+</body>
+</html>"#
+        )
+    }
+
+    #[test]
+    fn can_parse_correct() {
+        let body = page(
+            r#"That's the right answer!  You are <span class="day-success">one gold star</span> closer to restoring snow operations. [<a href="/2023/day/1#part2">Continue to Part Two</a>]"#,
+        );
+        assert_eq!(parse_response(&body), Outcome::Correct);
+    }
+
+    #[test]
+    fn can_parse_wrong() {
+        let body = page(
+            r#"That's not the right answer.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2023/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(parse_response(&body), Outcome::Wrong);
+    }
+
+    #[test]
+    fn can_parse_too_high_and_low() {
+        let body = page(
+            r#"That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(parse_response(&body), Outcome::TooHigh);
+        let body = page(
+            r#"That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data. [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(parse_response(&body), Outcome::TooLow);
+    }
+
+    #[test]
+    fn can_parse_rate_limited() {
+        let body = page(
+            r#"You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 28s left to wait. [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(
+            parse_response(&body),
+            Outcome::RateLimited(Some(Duration::from_secs(268)))
+        );
+        let body = page(
+            r#"You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait. [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(
+            parse_response(&body),
+            Outcome::RateLimited(Some(Duration::from_secs(37)))
+        );
+    }
+
+    #[test]
+    fn can_parse_already_solved() {
+        let body = page(
+            r#"You don't seem to be solving the right level.  Did you already complete it? [<a href="/2023/day/1">Return to Day 1</a>]"#,
+        );
+        assert_eq!(parse_response(&body), Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn unknown_responses_keep_their_text() {
+        let body = page("Something <em>new</em> happened.");
+        assert_eq!(
+            parse_response(&body),
+            Outcome::Unknown("Something new happened.".to_string())
+        );
+    }
+
+    #[test]
+    fn can_submit_to_stand_in_server() {
+        let body = page("That's the right answer!  You are one gold star closer.");
+        let (base_url, server) = stand_in::serve_once("200 OK", "text/html", body);
+
+        let outcome = submit_answer(&base_url, "abc123", 2022, 7, Part::Two, "5905").unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+
+        let request = server.join().unwrap();
+        assert_eq!(request.line, "POST /2022/day/7/answer HTTP/1.1");
+        assert_eq!(request.header("cookie"), Some("session=abc123"));
+        assert_eq!(request.body, "level=2&answer=5905");
+    }
+}
@@ -0,0 +1,196 @@
+use crate::cli::Headless;
+use crate::{inputs, puzzle};
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::time::Duration;
+
+/// How many times to try downloading once a puzzle has unlocked, since the
+/// servers are busy right then.
+const ATTEMPTS: u32 = 10;
+
+/// The next day to unlock after `now`, and when it does.
+pub fn next_unlock(
+    year: u32,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<(u32, chrono::DateTime<chrono::FixedOffset>)> {
+    (1..=25)
+        .map(|day| (day, inputs::unlock_time(year, day)))
+        .find(|(_, unlock)| *unlock > now)
+}
+
+fn format_countdown(remaining: chrono::Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, hours) = (seconds / 86400, seconds / 3600 % 24);
+    let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// A little randomness so that everyone waiting on the same puzzle doesn't
+/// retry in lockstep.
+fn jitter() -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_millis(u64::from(nanos % 1000))
+}
+
+/// Backs off linearly, up to 10s between attempts.
+pub fn retry_delay(attempt: u32, jitter: Duration) -> Duration {
+    Duration::from_secs(u64::from(attempt.min(10))) + jitter
+}
+
+/// Calls `f` until it succeeds, waiting `delay(attempt)` between attempts and
+/// giving up with the last error after `attempts` tries.
+pub fn with_retries<T>(
+    attempts: u32,
+    delay: impl Fn(u32) -> Duration,
+    mut f: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                eprintln!("Attempt {attempt} failed: {e:#}");
+                std::thread::sleep(delay(attempt));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Counts down to the given day unlocking (or the next one to, if no day is
+/// given), then downloads its input and puzzle. Returns the day.
+pub fn wait_for_unlock(year: u32, day: Option<u32>, headless: Headless) -> Result<u32> {
+    if !headless.can_download() {
+        bail!("Can't wait for puzzles without downloading them");
+    }
+    let (day, unlock) = match day {
+        Some(day) => (day, inputs::unlock_time(year, day)),
+        None => match next_unlock(year, chrono::Utc::now()) {
+            Some(next) => next,
+            None => bail!("Every {year} puzzle has already unlocked"),
+        },
+    };
+    // ask for the session now rather than when the puzzle unlocks
+    let session =
+        inputs::get_session(headless).with_context(|| "Failed to get AOC session auth")?;
+
+    let mut stderr = std::io::stderr();
+    loop {
+        let remaining = unlock.with_timezone(&chrono::Utc) - chrono::Utc::now();
+        if remaining <= chrono::Duration::zero() {
+            break;
+        }
+        write!(
+            stderr,
+            "\r{year} day {day} unlocks in {}  ",
+            format_countdown(remaining)
+        )?;
+        stderr.flush()?;
+        let tick = remaining.to_std().unwrap_or_default();
+        std::thread::sleep(tick.min(Duration::from_secs(1)));
+    }
+    writeln!(stderr, "\r{year} day {day} has unlocked!{:20}", "")?;
+
+    if !inputs::input_path(year, day).exists() {
+        with_retries(
+            ATTEMPTS,
+            |a| retry_delay(a, jitter()),
+            || inputs::download_input(year, day, &session),
+        )
+        .with_context(|| format!("Failed to download the input for {year} day {day}"))?;
+        eprintln!("Downloaded {year} day {day}!");
+    }
+    if !puzzle::puzzle_path(year, day).exists() {
+        with_retries(
+            ATTEMPTS,
+            |a| retry_delay(a, jitter()),
+            || puzzle::download_puzzle(year, day, &session),
+        )
+        .with_context(|| format!("Failed to download the puzzle for {year} day {day}"))?;
+    }
+    Ok(day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn finds_next_unlock() {
+        let now = chrono::Utc.with_ymd_and_hms(2023, 12, 5, 4, 59, 0).unwrap();
+        let (day, unlock) = next_unlock(2023, now).unwrap();
+        assert_eq!(day, 5);
+        assert_eq!(unlock.to_rfc3339(), "2023-12-05T00:00:01-05:00");
+
+        let now = chrono::Utc.with_ymd_and_hms(2023, 12, 5, 5, 1, 0).unwrap();
+        assert_eq!(next_unlock(2023, now).unwrap().0, 6);
+
+        let now = chrono::Utc.with_ymd_and_hms(2023, 11, 20, 0, 0, 0).unwrap();
+        assert_eq!(next_unlock(2023, now).unwrap().0, 1);
+
+        let now = chrono::Utc.with_ymd_and_hms(2023, 12, 26, 0, 0, 0).unwrap();
+        assert_eq!(next_unlock(2023, now), None);
+    }
+
+    #[test]
+    fn can_format_countdowns() {
+        assert_eq!(format_countdown(chrono::Duration::seconds(59)), "00:00:59");
+        assert_eq!(
+            format_countdown(chrono::Duration::seconds(3723)),
+            "01:02:03"
+        );
+        assert_eq!(
+            format_countdown(chrono::Duration::seconds(90000)),
+            "1d 01:00:00"
+        );
+        assert_eq!(format_countdown(chrono::Duration::seconds(-5)), "00:00:00");
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let mut calls = 0;
+        let value = with_retries(
+            5,
+            |_| Duration::ZERO,
+            || {
+                calls += 1;
+                if calls < 3 {
+                    bail!("not yet");
+                }
+                Ok(calls)
+            },
+        )
+        .unwrap();
+        assert_eq!(value, 3);
+
+        let mut calls = 0;
+        let err = with_retries(
+            3,
+            |_| Duration::ZERO,
+            || -> Result<()> {
+                calls += 1;
+                bail!("still not ready")
+            },
+        )
+        .unwrap_err();
+        assert_eq!(calls, 3);
+        assert_eq!(err.to_string(), "still not ready");
+    }
+
+    #[test]
+    fn delays_back_off() {
+        let jitter = Duration::from_millis(250);
+        assert_eq!(retry_delay(1, jitter), Duration::from_millis(1250));
+        assert_eq!(retry_delay(4, jitter), Duration::from_millis(4250));
+        assert_eq!(retry_delay(50, jitter), Duration::from_millis(10250));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::cli::Headless;
+use crate::{inputs, samples, scaffold};
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Everything a day's answers depend on.
+pub fn watched_paths(year: u32, day: u32) -> Vec<PathBuf> {
+    vec![
+        scaffold::day_dir(year, day),
+        Path::new("src").join("grid.rs"),
+        inputs::input_path(year, day),
+        samples::sample_path(year, day),
+    ]
+}
+
+/// When each of the files under the given paths was last modified. Paths
+/// that don't exist are left out, so creating them counts as a change.
+pub fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut pending: Vec<PathBuf> = paths.to_vec();
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(&path) {
+                pending.extend(entries.flatten().map(|e| e.path()));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            files.insert(path, modified);
+        }
+    }
+    files
+}
+
+/// Both parts' answers from `run --format json`, for a single day.
+pub fn answers_from_json(json: &str) -> Option<[Option<String>; 2]> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let day = value.as_array()?.first()?;
+    let answer = |part: &str| {
+        day.get(part)?
+            .get("answer")?
+            .as_str()
+            .map(|a| a.to_string())
+    };
+    Some([answer("part1"), answer("part2")])
+}
+
+/// How each part's answer changed since the previous run.
+pub fn diff(previous: Option<&[Option<String>; 2]>, current: &[Option<String>; 2]) -> Vec<String> {
+    let previous = previous.cloned().unwrap_or_default();
+    previous
+        .iter()
+        .zip(current.iter())
+        .enumerate()
+        .map(|(i, (before, after))| {
+            let part = i + 1;
+            match (before, after) {
+                (_, None) => format!("part {part}: no answer"),
+                (None, Some(after)) => format!("part {part}: {after}"),
+                (Some(before), Some(after)) if before == after => {
+                    format!("part {part}: {after} (unchanged)")
+                }
+                (Some(before), Some(after)) => format!("part {part}: {before} → {after}"),
+            }
+        })
+        .collect()
+}
+
+fn heading(stdout: &mut StandardStream, text: &str) -> Result<()> {
+    stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)).set_bold(true))?;
+    write!(stdout, "── {text} ──")?;
+    stdout.reset()?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Runs this binary through cargo, so that it's rebuilt with the latest
+/// changes first.
+fn cargo_run(year: u32, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command.args(["run", "--quiet", "--"]).args(args);
+    command.args(["--no-download", "--year", &year.to_string()]);
+    command
+}
+
+/// Rebuilds, checks the samples and then runs the real input, returning the
+/// answers if it got that far.
+fn run_once(year: u32, day: u32) -> Result<Option<[Option<String>; 2]>> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    heading(&mut stdout, "building")?;
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--quiet"])
+        .status()
+        .context("Failed to run cargo build")?;
+    if !status.success() {
+        return Ok(None);
+    }
+
+    heading(&mut stdout, "samples")?;
+    let status = cargo_run(year, &["test", &day.to_string()])
+        .status()
+        .context("Failed to run the samples")?;
+    if !status.success() {
+        return Ok(None);
+    }
+
+    heading(&mut stdout, "input")?;
+    let output = cargo_run(year, &["run", &day.to_string(), "--format", "json"])
+        .output()
+        .context("Failed to run the input")?;
+    std::io::stderr().write_all(&output.stderr)?;
+    Ok(answers_from_json(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Re-runs a day whenever its source, `grid.rs`, its samples or its input
+/// change, showing how the answers changed each time.
+pub fn watch(year: u32, day: u32, headless: Headless) -> Result<()> {
+    // fetch what we can up front, since the runs in the loop won't
+    inputs::load_inputs(year, &[day], headless)?;
+    if let Err(e) = samples::load_samples(year, day, headless) {
+        eprintln!("Failed to load samples for {year} day {day}: {e:#}");
+    }
+
+    let paths = watched_paths(year, day);
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+    let mut previous = None;
+    let mut seen = BTreeMap::new();
+    loop {
+        let current = snapshot(&paths);
+        if current == seen {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        // anything changed while running gets picked up next time round
+        seen = current;
+
+        if let Some(answers) = run_once(year, day)? {
+            for line in diff(previous.as_ref(), &answers) {
+                writeln!(stdout, "{line}")?;
+            }
+            previous = Some(answers);
+        }
+        heading(
+            &mut stdout,
+            &format!("watching {year} day {day} for changes"),
+        )?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn watches_the_days_own_year() {
+        let paths = watched_paths(2023, 17);
+        assert!(paths.contains(&PathBuf::from("src/day17")), "{paths:?}");
+        assert!(paths.contains(&inputs::input_path(2023, 17)), "{paths:?}");
+
+        let paths = watched_paths(2022, 5);
+        assert!(
+            paths.contains(&PathBuf::from("src/y2022/day05")),
+            "{paths:?}"
+        );
+        assert!(!paths.contains(&PathBuf::from("src/day05")), "{paths:?}");
+        assert!(paths.contains(&inputs::input_path(2022, 5)), "{paths:?}");
+        assert!(paths.contains(&samples::sample_path(2022, 5)), "{paths:?}");
+    }
+
+    #[test]
+    fn snapshot_notices_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("day17");
+        let input = dir.path().join("day17.txt");
+        let paths = vec![source.clone(), input.clone()];
+        assert!(snapshot(&paths).is_empty());
+
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("mod.rs"), "fn main() {}").unwrap();
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+
+        std::fs::write(&input, "2413432311323\n").unwrap();
+        let after = snapshot(&paths);
+        assert_eq!(after.len(), 2);
+        assert_ne!(before, after);
+        assert_eq!(after, snapshot(&paths));
+    }
+
+    #[test]
+    fn can_read_answers() {
+        let json = r#"[{"year": 2023, "day": 17,
+            "part1": {"answer": "102", "status": "Done", "elapsed": 0.1},
+            "part2": {"answer": null, "status": "Pending", "elapsed": 0.0},
+            "parse": 0.0, "elapsed": 0.1}]"#;
+        assert_eq!(
+            answers_from_json(json),
+            Some([Some("102".to_string()), None])
+        );
+        assert_eq!(answers_from_json("[]"), None);
+        assert_eq!(answers_from_json("error"), None);
+    }
+
+    #[test]
+    fn can_diff_answers() {
+        let first = [Some("102".to_string()), None];
+        assert_eq!(diff(None, &first), vec!["part 1: 102", "part 2: no answer"]);
+
+        let second = [Some("101".to_string()), Some("94".to_string())];
+        assert_eq!(
+            diff(Some(&first), &second),
+            vec!["part 1: 102 → 101", "part 2: 94"]
+        );
+        assert_eq!(
+            diff(Some(&second), &second),
+            vec!["part 1: 101 (unchanged)", "part 2: 94 (unchanged)"]
+        );
+    }
+}